@@ -0,0 +1,33 @@
+-- Promote the amenity and camping attributes people search on out of the event_data
+-- JSON blob. These are VIRTUAL generated columns, so they always track event_data and
+-- don't need to be written by EventContext::create/update.
+-- Interim: 20261018000200_normalize_event_data replaces them with typed tables and
+-- drops these columns again.
+
+ALTER TABLE events ADD COLUMN has_showers INTEGER
+    GENERATED ALWAYS AS (coalesce(json_extract(event_data, '$.amenities.showers'), 0)) VIRTUAL;
+
+ALTER TABLE events ADD COLUMN has_potable_water INTEGER
+    GENERATED ALWAYS AS (coalesce(json_extract(event_data, '$.amenities.potable_water'), 0)) VIRTUAL;
+
+ALTER TABLE events ADD COLUMN has_wifi INTEGER
+    GENERATED ALWAYS AS (coalesce(json_extract(event_data, '$.amenities.wifi'), 0)) VIRTUAL;
+
+ALTER TABLE events ADD COLUMN is_pet_friendly INTEGER
+    GENERATED ALWAYS AS (coalesce(json_extract(event_data, '$.camping_info.pet_friendly'), 0)) VIRTUAL;
+
+ALTER TABLE events ADD COLUMN fires_allowed INTEGER
+    GENERATED ALWAYS AS (coalesce(json_extract(event_data, '$.camping_info.fires_allowed'), 0)) VIRTUAL;
+
+-- hookups_available may be an object or a bare boolean (AI-enriched data, see deserialize_hookups)
+ALTER TABLE events ADD COLUMN has_hookups INTEGER
+    GENERATED ALWAYS AS (
+        CASE json_type(event_data, '$.camping_info.rv_camping.hookups_available')
+            WHEN 'object' THEN 1
+            WHEN 'true' THEN 1
+            ELSE 0
+        END
+    ) VIRTUAL;
+
+ALTER TABLE events ADD COLUMN has_dump_station INTEGER
+    GENERATED ALWAYS AS (coalesce(json_extract(event_data, '$.camping_info.rv_camping.dump_station'), 0)) VIRTUAL;
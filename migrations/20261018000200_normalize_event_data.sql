@@ -0,0 +1,229 @@
+-- Normalize the event_data JSON blob into typed columns and tables.
+-- event_data is still written on create/update for now, but reads come from here.
+
+-- ---------------------------------------------------------------------------
+-- Location and date details that only lived in the JSON
+-- ---------------------------------------------------------------------------
+ALTER TABLE events ADD COLUMN address TEXT NOT NULL DEFAULT '';
+ALTER TABLE events ADD COLUMN venue_name TEXT;
+ALTER TABLE events ADD COLUMN parking_info TEXT;
+ALTER TABLE events ADD COLUMN single_day INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE events ADD COLUMN early_arrival_available INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE events ADD COLUMN early_arrival_date TEXT;
+ALTER TABLE events ADD COLUMN late_departure_available INTEGER NOT NULL DEFAULT 0;
+
+UPDATE events SET
+    address = coalesce(json_extract(event_data, '$.location_info.address'), ''),
+    venue_name = json_extract(event_data, '$.location_info.venue_name'),
+    parking_info = json_extract(event_data, '$.location_info.parking_info'),
+    start_date = coalesce(json_extract(event_data, '$.date_info.start_date'), start_date),
+    end_date = coalesce(json_extract(event_data, '$.date_info.end_date'), end_date),
+    single_day = coalesce(json_extract(event_data, '$.date_info.single_day'), 0),
+    early_arrival_available = coalesce(json_extract(event_data, '$.date_info.early_arrival_available'), 0),
+    early_arrival_date = json_extract(event_data, '$.date_info.early_arrival_date'),
+    late_departure_available = coalesce(json_extract(event_data, '$.date_info.late_departure_available'), 0)
+WHERE json_valid(event_data);
+
+-- ---------------------------------------------------------------------------
+-- Amenities (row exists only when the event has amenities)
+-- ---------------------------------------------------------------------------
+CREATE TABLE event_amenities (
+    event_id INTEGER PRIMARY KEY REFERENCES events(id) ON DELETE CASCADE,
+    bathrooms INTEGER NOT NULL DEFAULT 0,
+    showers INTEGER NOT NULL DEFAULT 0,
+    potable_water INTEGER NOT NULL DEFAULT 0,
+    wifi INTEGER NOT NULL DEFAULT 0,
+    cell_service_quality TEXT,
+    firewood_available INTEGER NOT NULL DEFAULT 0,
+    ice_available INTEGER NOT NULL DEFAULT 0,
+    trash_service INTEGER NOT NULL DEFAULT 0,
+    recycling INTEGER NOT NULL DEFAULT 0,
+    laundry INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT INTO event_amenities (
+    event_id, bathrooms, showers, potable_water, wifi, cell_service_quality,
+    firewood_available, ice_available, trash_service, recycling, laundry
+)
+SELECT
+    id,
+    coalesce(json_extract(event_data, '$.amenities.bathrooms'), 0),
+    coalesce(json_extract(event_data, '$.amenities.showers'), 0),
+    coalesce(json_extract(event_data, '$.amenities.potable_water'), 0),
+    coalesce(json_extract(event_data, '$.amenities.wifi'), 0),
+    json_extract(event_data, '$.amenities.cell_service_quality'),
+    coalesce(json_extract(event_data, '$.amenities.firewood_available'), 0),
+    coalesce(json_extract(event_data, '$.amenities.ice_available'), 0),
+    coalesce(json_extract(event_data, '$.amenities.trash_service'), 0),
+    coalesce(json_extract(event_data, '$.amenities.recycling'), 0),
+    coalesce(json_extract(event_data, '$.amenities.laundry'), 0)
+FROM events
+WHERE json_valid(event_data) AND json_type(event_data, '$.amenities') = 'object';
+
+-- ---------------------------------------------------------------------------
+-- Camping info, vehicle camping flattened in. camping_allowed stays on events.
+-- ---------------------------------------------------------------------------
+CREATE TABLE event_camping_info (
+    event_id INTEGER PRIMARY KEY REFERENCES events(id) ON DELETE CASCADE,
+    walking_distance INTEGER NOT NULL DEFAULT 0,
+    tent_camping INTEGER NOT NULL DEFAULT 0,
+    campsite_reservations_required INTEGER NOT NULL DEFAULT 0,
+    primitive_camping INTEGER NOT NULL DEFAULT 0,
+    developed_campsites INTEGER NOT NULL DEFAULT 0,
+    max_stay_nights INTEGER,
+    pet_friendly INTEGER NOT NULL DEFAULT 0,
+    quiet_hours TEXT,
+    fires_allowed INTEGER NOT NULL DEFAULT 0,
+    van_camping INTEGER NOT NULL DEFAULT 0,
+    car_camping INTEGER NOT NULL DEFAULT 0,
+    truck_camping INTEGER NOT NULL DEFAULT 0,
+    rooftop_tent_allowed INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT INTO event_camping_info (
+    event_id, walking_distance, tent_camping, campsite_reservations_required,
+    primitive_camping, developed_campsites, max_stay_nights, pet_friendly, quiet_hours,
+    fires_allowed, van_camping, car_camping, truck_camping, rooftop_tent_allowed
+)
+SELECT
+    id,
+    coalesce(json_extract(event_data, '$.camping_info.walking_distance'), 0),
+    coalesce(json_extract(event_data, '$.camping_info.tent_camping'), 0),
+    coalesce(json_extract(event_data, '$.camping_info.campsite_reservations_required'), 0),
+    coalesce(json_extract(event_data, '$.camping_info.primitive_camping'), 0),
+    coalesce(json_extract(event_data, '$.camping_info.developed_campsites'), 0),
+    json_extract(event_data, '$.camping_info.max_stay_nights'),
+    coalesce(json_extract(event_data, '$.camping_info.pet_friendly'), 0),
+    json_extract(event_data, '$.camping_info.quiet_hours'),
+    coalesce(json_extract(event_data, '$.camping_info.fires_allowed'), 0),
+    coalesce(json_extract(event_data, '$.camping_info.vehicle_camping.van_camping'), 0),
+    coalesce(json_extract(event_data, '$.camping_info.vehicle_camping.car_camping'), 0),
+    coalesce(json_extract(event_data, '$.camping_info.vehicle_camping.truck_camping'), 0),
+    coalesce(json_extract(event_data, '$.camping_info.vehicle_camping.rooftop_tent_allowed'), 0)
+FROM events
+WHERE json_valid(event_data) AND json_type(event_data, '$.camping_info') = 'object';
+
+-- ---------------------------------------------------------------------------
+-- RV options (one row per camping_info row), hookups flattened in
+-- ---------------------------------------------------------------------------
+CREATE TABLE event_rv_options (
+    event_id INTEGER PRIMARY KEY REFERENCES events(id) ON DELETE CASCADE,
+    rv_allowed INTEGER NOT NULL DEFAULT 0,
+    class_a_allowed INTEGER NOT NULL DEFAULT 0,
+    class_b_allowed INTEGER NOT NULL DEFAULT 0,
+    class_c_allowed INTEGER NOT NULL DEFAULT 0,
+    travel_trailers_allowed INTEGER NOT NULL DEFAULT 0,
+    fifth_wheel_allowed INTEGER NOT NULL DEFAULT 0,
+    max_length_feet INTEGER,
+    max_width_feet INTEGER,
+    hookups_available INTEGER NOT NULL DEFAULT 0,
+    hookup_electric INTEGER NOT NULL DEFAULT 0,
+    hookup_water INTEGER NOT NULL DEFAULT 0,
+    hookup_sewer INTEGER NOT NULL DEFAULT 0,
+    hookup_amp_service TEXT,
+    dump_station INTEGER NOT NULL DEFAULT 0
+);
+
+-- hookups_available may be a bare boolean in AI-enriched data, same rules as deserialize_hookups
+INSERT INTO event_rv_options (
+    event_id, rv_allowed, class_a_allowed, class_b_allowed, class_c_allowed,
+    travel_trailers_allowed, fifth_wheel_allowed, max_length_feet, max_width_feet,
+    hookups_available, hookup_electric, hookup_water, hookup_sewer, hookup_amp_service,
+    dump_station
+)
+SELECT
+    id,
+    coalesce(json_extract(event_data, '$.camping_info.rv_camping.allowed'), 0),
+    coalesce(json_extract(event_data, '$.camping_info.rv_camping.class_a_allowed'), 0),
+    coalesce(json_extract(event_data, '$.camping_info.rv_camping.class_b_allowed'), 0),
+    coalesce(json_extract(event_data, '$.camping_info.rv_camping.class_c_allowed'), 0),
+    coalesce(json_extract(event_data, '$.camping_info.rv_camping.travel_trailers_allowed'), 0),
+    coalesce(json_extract(event_data, '$.camping_info.rv_camping.fifth_wheel_allowed'), 0),
+    json_extract(event_data, '$.camping_info.rv_camping.max_length_feet'),
+    json_extract(event_data, '$.camping_info.rv_camping.max_width_feet'),
+    CASE json_type(event_data, '$.camping_info.rv_camping.hookups_available')
+        WHEN 'object' THEN 1 WHEN 'true' THEN 1 ELSE 0 END,
+    CASE json_type(event_data, '$.camping_info.rv_camping.hookups_available')
+        WHEN 'true' THEN 1
+        ELSE coalesce(json_extract(event_data, '$.camping_info.rv_camping.hookups_available.electric'), 0) END,
+    CASE json_type(event_data, '$.camping_info.rv_camping.hookups_available')
+        WHEN 'true' THEN 1
+        ELSE coalesce(json_extract(event_data, '$.camping_info.rv_camping.hookups_available.water'), 0) END,
+    CASE json_type(event_data, '$.camping_info.rv_camping.hookups_available')
+        WHEN 'true' THEN 1
+        ELSE coalesce(json_extract(event_data, '$.camping_info.rv_camping.hookups_available.sewer'), 0) END,
+    json_extract(event_data, '$.camping_info.rv_camping.hookups_available.amp_service'),
+    coalesce(json_extract(event_data, '$.camping_info.rv_camping.dump_station'), 0)
+FROM events
+WHERE json_valid(event_data) AND json_type(event_data, '$.camping_info') = 'object';
+
+-- ---------------------------------------------------------------------------
+-- Generator options, quiet hours flattened in (days stored comma separated)
+-- ---------------------------------------------------------------------------
+CREATE TABLE event_generator_options (
+    event_id INTEGER PRIMARY KEY REFERENCES events(id) ON DELETE CASCADE,
+    generators_allowed INTEGER NOT NULL DEFAULT 0,
+    max_decibel_limit INTEGER,
+    inverter_generators_only INTEGER NOT NULL DEFAULT 0,
+    propane_generators_allowed INTEGER NOT NULL DEFAULT 0,
+    gasoline_generators_allowed INTEGER NOT NULL DEFAULT 0,
+    diesel_generators_allowed INTEGER NOT NULL DEFAULT 0,
+    designated_generator_areas INTEGER NOT NULL DEFAULT 0,
+    distance_from_neighbors_feet INTEGER,
+    fuel_storage_restrictions TEXT,
+    has_quiet_hours INTEGER NOT NULL DEFAULT 0,
+    quiet_hours_all_day INTEGER NOT NULL DEFAULT 0,
+    quiet_hours_start TEXT,
+    quiet_hours_end TEXT,
+    quiet_hours_days TEXT
+);
+
+-- generator_options may be a bare boolean, same rules as deserialize_generator_options
+INSERT INTO event_generator_options (
+    event_id, generators_allowed, max_decibel_limit, inverter_generators_only,
+    propane_generators_allowed, gasoline_generators_allowed, diesel_generators_allowed,
+    designated_generator_areas, distance_from_neighbors_feet, fuel_storage_restrictions,
+    has_quiet_hours, quiet_hours_all_day, quiet_hours_start, quiet_hours_end, quiet_hours_days
+)
+SELECT
+    id,
+    CASE json_type(event_data, '$.camping_info.generator_options')
+        WHEN 'true' THEN 1 WHEN 'false' THEN 0
+        ELSE coalesce(json_extract(event_data, '$.camping_info.generator_options.generators_allowed'), 0) END,
+    json_extract(event_data, '$.camping_info.generator_options.max_decibel_limit'),
+    coalesce(json_extract(event_data, '$.camping_info.generator_options.inverter_generators_only'), 0),
+    CASE json_type(event_data, '$.camping_info.generator_options')
+        WHEN 'true' THEN 1
+        ELSE coalesce(json_extract(event_data, '$.camping_info.generator_options.propane_generators_allowed'), 0) END,
+    CASE json_type(event_data, '$.camping_info.generator_options')
+        WHEN 'true' THEN 1
+        ELSE coalesce(json_extract(event_data, '$.camping_info.generator_options.gasoline_generators_allowed'), 0) END,
+    CASE json_type(event_data, '$.camping_info.generator_options')
+        WHEN 'true' THEN 1
+        ELSE coalesce(json_extract(event_data, '$.camping_info.generator_options.diesel_generators_allowed'), 0) END,
+    coalesce(json_extract(event_data, '$.camping_info.generator_options.designated_generator_areas'), 0),
+    json_extract(event_data, '$.camping_info.generator_options.distance_from_neighbors_feet'),
+    json_extract(event_data, '$.camping_info.generator_options.fuel_storage_restrictions'),
+    coalesce(json_type(event_data, '$.camping_info.generator_options.quiet_hours') = 'object', 0),
+    coalesce(json_extract(event_data, '$.camping_info.generator_options.quiet_hours.all_day_restriction'), 0),
+    json_extract(event_data, '$.camping_info.generator_options.quiet_hours.start_time'),
+    json_extract(event_data, '$.camping_info.generator_options.quiet_hours.end_time'),
+    (SELECT group_concat(value, ',')
+       FROM json_each(events.event_data, '$.camping_info.generator_options.quiet_hours.days_of_week'))
+FROM events
+WHERE json_valid(event_data)
+  AND json_type(event_data, '$.camping_info.generator_options') IN ('object', 'true', 'false');
+
+-- ---------------------------------------------------------------------------
+-- Replaces the generated search columns from 20261018000100_event_search_columns.
+-- Those were the interim way to filter on amenities and camping while the
+-- values only lived in event_data. Search now filters on the typed tables
+-- above, so the seven generated columns are dropped here on purpose.
+-- ---------------------------------------------------------------------------
+ALTER TABLE events DROP COLUMN has_showers;
+ALTER TABLE events DROP COLUMN has_potable_water;
+ALTER TABLE events DROP COLUMN has_wifi;
+ALTER TABLE events DROP COLUMN is_pet_friendly;
+ALTER TABLE events DROP COLUMN fires_allowed;
+ALTER TABLE events DROP COLUMN has_hookups;
+ALTER TABLE events DROP COLUMN has_dump_station;
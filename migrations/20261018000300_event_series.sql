@@ -0,0 +1,15 @@
+-- Event series group the yearly editions of the same festival.
+CREATE TABLE event_series (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    description TEXT,
+    website TEXT,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+ALTER TABLE events ADD COLUMN series_id INTEGER REFERENCES event_series(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_events_series_id ON events(series_id);
+
+-- Series favorites live next to the other id lists
+ALTER TABLE user_event_data ADD COLUMN favorite_series TEXT NOT NULL DEFAULT '[]';
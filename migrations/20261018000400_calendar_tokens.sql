@@ -0,0 +1,11 @@
+-- Secret tokens for subscribable calendar feeds. Calendar apps can't send a
+-- Bearer JWT, so the token goes in the URL. Only its SHA-256 hash is stored.
+CREATE TABLE calendar_tokens (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    revoked_at TEXT
+);
+
+CREATE INDEX idx_calendar_tokens_user_id ON calendar_tokens(user_id);
@@ -0,0 +1,17 @@
+-- When an event was last created or updated, drives the saved search alerts
+ALTER TABLE events ADD COLUMN modified_at TEXT;
+
+CREATE INDEX idx_events_modified_at ON events(modified_at);
+
+CREATE TABLE saved_searches (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    query TEXT NOT NULL,                 -- EventQueryParams as JSON
+    alerts_enabled INTEGER NOT NULL DEFAULT 1,
+    last_checked_at TEXT NOT NULL,       -- events modified at or after this are new matches
+    checked_event_ids TEXT NOT NULL DEFAULT '[]', -- already handled events modified exactly at last_checked_at
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_saved_searches_user_id ON saved_searches(user_id);
@@ -0,0 +1,13 @@
+-- In-app notification center, written through NotificationService
+CREATE TABLE notifications (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id TEXT NOT NULL,
+    category TEXT NOT NULL,
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    event_id INTEGER REFERENCES events(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    read_at TEXT
+);
+
+CREATE INDEX idx_notifications_user_id ON notifications(user_id, read_at);
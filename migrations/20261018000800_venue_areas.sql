@@ -0,0 +1,21 @@
+-- Stages, workshop tents, camp zones and the like inside one event's grounds.
+-- A point (latitude/longitude), an outline (polygon, a JSON ring of
+-- [longitude, latitude] pairs) or neither, for venues without a map yet.
+CREATE TABLE venue_areas (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL DEFAULT 'other',
+    description TEXT,
+    latitude REAL,
+    longitude REAL,
+    polygon TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX idx_venue_areas_event_id ON venue_areas(event_id);
+
+ALTER TABLE microevents ADD COLUMN area_id INTEGER REFERENCES venue_areas(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_microevents_area_id ON microevents(area_id);
@@ -0,0 +1,17 @@
+-- Tracks (workshops, music, kids, meals ...) group an event's microevents.
+-- Each event has its own set, managed by the event owner.
+CREATE TABLE microevent_tracks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    description TEXT,
+    color TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE UNIQUE INDEX idx_microevent_tracks_event_name ON microevent_tracks(event_id, name COLLATE NOCASE);
+
+ALTER TABLE microevents ADD COLUMN track_id INTEGER REFERENCES microevent_tracks(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_microevents_track_id ON microevents(track_id);
@@ -0,0 +1,12 @@
+-- Recurring microevents. The first occurrence holds the rule (an RRULE
+-- subset) and the rest are ordinary rows pointing back at it, one per date,
+-- so saves, favorites and schedules keep working per occurrence.
+-- detached: edited on its own, regenerating the series leaves it alone.
+-- cancelled: this occurrence is off, it stays so regenerating doesn't bring it back.
+ALTER TABLE microevents ADD COLUMN recurrence_rule TEXT;
+ALTER TABLE microevents ADD COLUMN recurrence_parent_id INTEGER REFERENCES microevents(id) ON DELETE CASCADE;
+ALTER TABLE microevents ADD COLUMN occurrence_date TEXT;
+ALTER TABLE microevents ADD COLUMN detached BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE microevents ADD COLUMN cancelled BOOLEAN NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_microevents_recurrence_parent_id ON microevents(recurrence_parent_id);
@@ -0,0 +1,17 @@
+-- RSVPs for microevents with limited seats. capacity NULL means unlimited.
+-- Seats go in RSVP order (id), anyone past capacity is waitlisted and moves
+-- up as seats free.
+ALTER TABLE microevents ADD COLUMN capacity INTEGER;
+
+CREATE TABLE microevent_rsvps (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    microevent_id INTEGER NOT NULL REFERENCES microevents(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL,
+    status TEXT NOT NULL, -- going, waitlisted
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    promoted_at TEXT,
+    UNIQUE (microevent_id, user_id)
+);
+
+CREATE INDEX idx_microevent_rsvps_status ON microevent_rsvps(microevent_id, status, id);
+CREATE INDEX idx_microevent_rsvps_user_id ON microevent_rsvps(user_id);
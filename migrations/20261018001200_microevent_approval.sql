@@ -0,0 +1,15 @@
+-- Event owners can require approval for microevents submitted by other users.
+-- A plain column, written by EventContext::create/update next to event_data.
+ALTER TABLE events ADD COLUMN require_microevent_approval INTEGER NOT NULL DEFAULT 0;
+
+-- Events saved before the column existed (unreadable blobs keep the default)
+UPDATE events
+SET require_microevent_approval = coalesce(json_extract(event_data, '$.require_microevent_approval'), 0)
+WHERE json_valid(event_data);
+
+-- approved, pending, rejected. Everything that exists today is approved.
+ALTER TABLE microevents ADD COLUMN approval_status TEXT NOT NULL DEFAULT 'approved';
+-- The owner's reason when rejecting
+ALTER TABLE microevents ADD COLUMN review_note TEXT;
+
+CREATE INDEX idx_microevents_approval_status ON microevents(event_id, approval_status);
@@ -0,0 +1,11 @@
+-- The event's IANA time zone (e.g. America/Denver), set on location_info.
+-- A plain column, written by EventContext::create/update next to event_data.
+ALTER TABLE events ADD COLUMN time_zone TEXT;
+
+-- Events saved before the column existed (unreadable blobs stay NULL)
+UPDATE events
+SET time_zone = json_extract(event_data, '$.location_info.time_zone')
+WHERE json_valid(event_data);
+
+-- "happening now" looks at one event's microevents by start time
+CREATE INDEX idx_microevents_event_start ON microevents(event_id, start_time);
@@ -0,0 +1,50 @@
+// ============================================================================
+// Repository: src/context/calendar_token_context.rs
+// ============================================================================
+
+use crate::errors::AppError;
+use sqlx::SqlitePool;
+
+pub struct CalendarTokenContext {
+    pool: SqlitePool,
+}
+
+impl CalendarTokenContext {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, user_id: &str, token_hash: &str) -> Result<i64, AppError> {
+        let result = sqlx::query("INSERT INTO calendar_tokens (user_id, token_hash) VALUES (?, ?)")
+            .bind(user_id)
+            .bind(token_hash)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// The owner of a token that has not been revoked
+    pub async fn find_user_id(&self, token_hash: &str) -> Result<Option<String>, AppError> {
+        let user_id = sqlx::query_scalar::<_, String>(
+            "SELECT user_id FROM calendar_tokens WHERE token_hash = ? AND revoked_at IS NULL",
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(user_id)
+    }
+
+    pub async fn revoke_all(&self, user_id: &str) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            "UPDATE calendar_tokens SET revoked_at = CURRENT_TIMESTAMP
+             WHERE user_id = ? AND revoked_at IS NULL",
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -5,6 +5,7 @@
 use crate::errors::AppError;
 use crate::models::database_models::CampingProfileRow;
 use crate::models::event_models::CampingProfile;
+use crate::models::stored_schema::{StoredKind, from_stored_json, to_stored_json};
 use sqlx::SqlitePool;
 
 pub struct CampingProfileContext {
@@ -26,7 +27,8 @@ impl CampingProfileContext {
         let profiles: Vec<CampingProfile> = rows
             .into_iter()
             .filter_map(|row| {
-                let mut profile: CampingProfile = serde_json::from_str(&row.camping_data).ok()?;
+                let mut profile: CampingProfile =
+                    from_stored_json(StoredKind::CampingProfile, &row.camping_data).ok()?;
                 profile.id = Some(row.id);
                 Some(profile)
             })
@@ -43,7 +45,8 @@ impl CampingProfileContext {
         .fetch_one(&self.pool)
         .await?;
 
-        let mut profile: CampingProfile = serde_json::from_str(&row.camping_data)?;
+        let mut profile: CampingProfile =
+            from_stored_json(StoredKind::CampingProfile, &row.camping_data)?;
         profile.id = Some(row.id);
         Ok(profile)
     }
@@ -58,7 +61,8 @@ impl CampingProfileContext {
 
         match result {
             Some(row) => {
-                let mut profile: CampingProfile = serde_json::from_str(&row.camping_data)?;
+                let mut profile: CampingProfile =
+                    from_stored_json(StoredKind::CampingProfile, &row.camping_data)?;
                 profile.id = Some(row.id);
                 Ok(Some(profile))
             }
@@ -67,7 +71,7 @@ impl CampingProfileContext {
     }
 
     pub async fn create(&self, profile: &CampingProfile) -> Result<i64, AppError> {
-        let camping_json = serde_json::to_string(profile)?;
+        let camping_json = to_stored_json(profile)?;
 
         let result = sqlx::query(
             "INSERT INTO camping_profiles (profile_name, description, camping_data) VALUES (?, ?, ?)"
@@ -82,7 +86,7 @@ impl CampingProfileContext {
     }
 
     pub async fn update(&self, id: i64, profile: &CampingProfile) -> Result<bool, AppError> {
-        let camping_json = serde_json::to_string(profile)?;
+        let camping_json = to_stored_json(profile)?;
 
         let result = sqlx::query(
             "UPDATE camping_profiles SET profile_name = ?, description = ?, camping_data = ? WHERE id = ?"
@@ -97,6 +101,17 @@ impl CampingProfileContext {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Raw camping_data for every profile, used by the schema upgrade job
+    pub async fn find_all_camping_data(&self) -> Result<Vec<(i64, String)>, AppError> {
+        let rows = sqlx::query_as::<_, (i64, String)>(
+            "SELECT id, camping_data FROM camping_profiles ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
     pub async fn delete(&self, id: i64) -> Result<bool, AppError> {
         let result = sqlx::query("DELETE FROM camping_profiles WHERE id = ?")
             .bind(id)
@@ -0,0 +1,121 @@
+// ============================================================================
+// Repository: src/context/email_outbox_context.rs
+// ============================================================================
+
+use crate::errors::AppError;
+use crate::models::database_models::EmailOutboxRow;
+use crate::models::email_models::QueuedEmail;
+use sqlx::{SqliteConnection, SqlitePool};
+
+pub struct EmailOutboxContext {
+    pool: SqlitePool,
+}
+
+impl EmailOutboxContext {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn enqueue(&self, email: &QueuedEmail) -> Result<i64, AppError> {
+        let mut conn = self.pool.acquire().await?;
+        Self::insert(&mut conn, email).await
+    }
+
+    /// For callers that queue the message along with their own rows, in their transaction
+    pub async fn insert(conn: &mut SqliteConnection, email: &QueuedEmail) -> Result<i64, AppError> {
+        let result = sqlx::query(
+            "INSERT INTO email_outbox (to_address, message_type, subject, body, next_attempt_at)
+             VALUES (?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))",
+        )
+        .bind(&email.to)
+        .bind(email.message_type)
+        .bind(&email.subject)
+        .bind(&email.body)
+        .execute(conn)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Pending messages whose next attempt is due, oldest first
+    pub async fn find_due(&self, now: &str, limit: i64) -> Result<Vec<EmailOutboxRow>, AppError> {
+        let rows = sqlx::query_as::<_, EmailOutboxRow>(
+            "SELECT id, to_address, message_type, subject, body, attempts
+             FROM email_outbox
+             WHERE status = 'pending' AND next_attempt_at <= ?
+             ORDER BY next_attempt_at, id
+             LIMIT ?",
+        )
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn mark_sent(&self, id: i64) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE email_outbox
+             SET status = 'sent', attempts = attempts + 1, last_error = NULL,
+                 sent_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+             WHERE id = ?",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn mark_retry(
+        &self,
+        id: i64,
+        next_attempt_at: &str,
+        error: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE email_outbox
+             SET attempts = attempts + 1, next_attempt_at = ?, last_error = ?
+             WHERE id = ?",
+        )
+        .bind(next_attempt_at)
+        .bind(error)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn mark_failed(&self, id: i64, error: &str) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE email_outbox
+             SET status = 'failed', attempts = attempts + 1, last_error = ?
+             WHERE id = ?",
+        )
+        .bind(error)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// When a message of this type was last queued for the address
+    pub async fn last_queued_at(
+        &self,
+        to_address: &str,
+        message_type: &str,
+    ) -> Result<Option<String>, AppError> {
+        let created_at = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT max(created_at) FROM email_outbox WHERE to_address = ? AND message_type = ?",
+        )
+        .bind(to_address)
+        .bind(message_type)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created_at)
+    }
+}
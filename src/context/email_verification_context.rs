@@ -0,0 +1,75 @@
+// ============================================================================
+// Repository: src/context/email_verification_context.rs
+// ============================================================================
+
+use crate::errors::AppError;
+use sqlx::{Row, SqlitePool};
+
+pub struct EmailVerificationContext {
+    pool: SqlitePool,
+}
+
+impl EmailVerificationContext {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(
+        &self,
+        token_hash: &str,
+        user_id: &str,
+        email: &str,
+        expires_at: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO email_verification_tokens (token_hash, user_id, email, expires_at)
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(token_hash)
+        .bind(user_id)
+        .bind(email)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Marks the user verified if the token is valid and the address has not
+    /// changed since it was sent. Tokens are single use either way.
+    pub async fn consume(&self, token_hash: &str, now: &str) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query(
+            "DELETE FROM email_verification_tokens
+             WHERE token_hash = ? AND expires_at > ?
+             RETURNING user_id, email",
+        )
+        .bind(token_hash)
+        .bind(now)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(false);
+        };
+        let user_id: String = row.get("user_id");
+        let email: String = row.get("email");
+
+        let result = sqlx::query("UPDATE users SET email_verified = 1 WHERE id = ? AND email = ?")
+            .bind(&user_id)
+            .bind(&email)
+            .execute(&mut *tx)
+            .await?;
+
+        // older links for the same user are no longer needed
+        sqlx::query("DELETE FROM email_verification_tokens WHERE user_id = ?")
+            .bind(&user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -2,8 +2,49 @@
 
 use crate::errors::AppError;
 use crate::models::database_models::EventRow;
+use crate::models::dto::EventQueryParams;
 use crate::models::event_models::NomEvent;
-use sqlx::SqlitePool;
+use crate::models::stored_schema::{StoredKind, from_stored_json, to_stored_json};
+use chrono::SecondsFormat;
+use sqlx::{Sqlite, SqlitePool, Transaction};
+
+// Every event read goes through this, the detail tables are optional so they are LEFT JOINed
+const EVENT_SELECT: &str = "SELECT
+        e.id, e.name, e.description, e.website, e.event_type_id,
+        e.latitude, e.longitude, e.start_date, e.end_date, e.camping_allowed, e.series_id,
+        (SELECT n.id FROM events n
+         WHERE n.series_id = e.series_id AND substr(n.start_date, 1, 10) > substr(e.start_date, 1, 10)
+         ORDER BY substr(n.start_date, 1, 10) LIMIT 1) as next_edition_id,
+        e.address, e.venue_name, e.parking_info, e.single_day,
+        e.early_arrival_available, e.early_arrival_date, e.late_departure_available,
+        e.require_microevent_approval, e.time_zone,
+        et.name as event_type_name,
+        et.description as event_type_description,
+        et.map_indicator as event_type_map_indicator,
+        et.category as event_type_category,
+        am.event_id as amenities_event_id, am.bathrooms, am.showers, am.potable_water, am.wifi,
+        am.cell_service_quality, am.firewood_available, am.ice_available, am.trash_service,
+        am.recycling, am.laundry,
+        ci.event_id as camping_event_id, ci.walking_distance, ci.tent_camping,
+        ci.campsite_reservations_required, ci.primitive_camping, ci.developed_campsites,
+        ci.max_stay_nights, ci.pet_friendly, ci.quiet_hours, ci.fires_allowed,
+        ci.van_camping, ci.car_camping, ci.truck_camping, ci.rooftop_tent_allowed,
+        rv.rv_allowed, rv.class_a_allowed, rv.class_b_allowed, rv.class_c_allowed,
+        rv.travel_trailers_allowed, rv.fifth_wheel_allowed, rv.max_length_feet, rv.max_width_feet,
+        rv.hookups_available, rv.hookup_electric, rv.hookup_water, rv.hookup_sewer,
+        rv.hookup_amp_service, rv.dump_station,
+        gen.event_id as generator_event_id, gen.generators_allowed, gen.max_decibel_limit,
+        gen.inverter_generators_only, gen.propane_generators_allowed,
+        gen.gasoline_generators_allowed, gen.diesel_generators_allowed,
+        gen.designated_generator_areas, gen.distance_from_neighbors_feet,
+        gen.fuel_storage_restrictions, gen.has_quiet_hours, gen.quiet_hours_all_day,
+        gen.quiet_hours_start, gen.quiet_hours_end, gen.quiet_hours_days
+     FROM events e
+     JOIN event_types et ON e.event_type_id = et.id
+     LEFT JOIN event_amenities am ON am.event_id = e.id
+     LEFT JOIN event_camping_info ci ON ci.event_id = e.id
+     LEFT JOIN event_rv_options rv ON rv.event_id = e.id
+     LEFT JOIN event_generator_options gen ON gen.event_id = e.id";
 
 pub struct EventContext {
     pool: SqlitePool,
@@ -15,39 +56,19 @@ impl EventContext {
         Self { pool }
     }
     pub async fn find_all(&self) -> Result<Vec<EventRow>, AppError> {
-        let rows = sqlx::query_as::<_, EventRow>(
-            "SELECT 
-                e.id, e.name, e.description, e.website, e.event_type_id, 
-                e.latitude, e.longitude, e.start_date, e.end_date, e.camping_allowed, e.event_data,
-                et.name as event_type_name,
-                et.description as event_type_description,
-                et.map_indicator as event_type_map_indicator,
-                et.category as event_type_category
-             FROM events e
-             JOIN event_types et ON e.event_type_id = et.id",
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        let rows = sqlx::query_as::<_, EventRow>(EVENT_SELECT)
+            .fetch_all(&self.pool)
+            .await?;
 
         Ok(rows)
     }
 
     pub async fn find_by_id(&self, id: i64) -> Result<EventRow, AppError> {
-        let row = sqlx::query_as::<_, EventRow>(
-            "SELECT 
-                e.id, e.name, e.description, e.website, e.event_type_id,
-                e.latitude, e.longitude, e.start_date, e.end_date, e.camping_allowed, e.event_data,
-                et.name as event_type_name,
-                et.description as event_type_description,
-                et.map_indicator as event_type_map_indicator,
-                et.category as event_type_category
-             FROM events e
-             JOIN event_types et ON e.event_type_id = et.id
-             WHERE e.id = ?",
-        )
-        .bind(id)
-        .fetch_one(&self.pool)
-        .await?;
+        let query_str = format!("{} WHERE e.id = ?", EVENT_SELECT);
+        let row = sqlx::query_as::<_, EventRow>(&query_str)
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
 
         Ok(row)
     }
@@ -56,19 +77,7 @@ impl EventContext {
         // Convert Vec to comma-separated string for SQL IN clause
         let placeholders = input.iter().map(|_| "?").collect::<Vec<_>>().join(",");
 
-        let query_str = format!(
-            "SELECT 
-            e.id, e.name, e.description, e.website, e.event_type_id,
-            e.latitude, e.longitude, e.start_date, e.end_date, e.camping_allowed, e.event_data,
-            et.name as event_type_name,
-            et.description as event_type_description,
-            et.map_indicator as event_type_map_indicator,
-            et.category as event_type_category
-         FROM events e
-         JOIN event_types et ON e.event_type_id = et.id
-         WHERE e.id IN ({})",
-            placeholders
-        );
+        let query_str = format!("{} WHERE e.id IN ({})", EVENT_SELECT, placeholders);
 
         let mut query = sqlx::query_as::<_, EventRow>(&query_str);
 
@@ -82,21 +91,11 @@ impl EventContext {
     }
 
     pub async fn find_by_type(&self, event_type_id: i64) -> Result<Vec<EventRow>, AppError> {
-        let rows = sqlx::query_as::<_, EventRow>(
-            "SELECT 
-                e.id, e.name, e.description, e.website, e.event_type_id,
-                e.latitude, e.longitude, e.start_date, e.end_date, e.camping_allowed, e.event_data,
-                et.name as event_type_name,
-                et.description as event_type_description,
-                et.map_indicator as event_type_map_indicator,
-                et.category as event_type_category
-             FROM events e
-             JOIN event_types et ON e.event_type_id = et.id
-             WHERE e.event_type_id = ?",
-        )
-        .bind(event_type_id)
-        .fetch_all(&self.pool)
-        .await?;
+        let query_str = format!("{} WHERE e.event_type_id = ?", EVENT_SELECT);
+        let rows = sqlx::query_as::<_, EventRow>(&query_str)
+            .bind(event_type_id)
+            .fetch_all(&self.pool)
+            .await?;
 
         Ok(rows)
     }
@@ -118,24 +117,17 @@ impl EventContext {
         let min_lon = lon - lon_delta;
         let max_lon = lon + lon_delta;
 
-        let query = r#"
-        SELECT
-            e.id, e.name, e.description, e.website, e.event_type_id,
-            e.latitude, e.longitude, e.start_date, e.end_date, e.camping_allowed, e.event_data,
-            et.name as event_type_name,
-            et.description as event_type_description,
-            et.map_indicator as event_type_map_indicator,
-            et.category as event_type_category
-        FROM events e
-        JOIN event_types et ON e.event_type_id = et.id
+        let query = format!(
+            "{}
         WHERE e.latitude IS NOT NULL 
         AND e.longitude IS NOT NULL
         AND e.latitude BETWEEN ? AND ?
         AND e.longitude BETWEEN ? AND ?
-        ORDER BY e.name
-    "#;
+        ORDER BY e.name",
+            EVENT_SELECT
+        );
 
-        let rows = sqlx::query_as::<_, EventRow>(query)
+        let rows = sqlx::query_as::<_, EventRow>(&query)
             .bind(min_lat)
             .bind(max_lat)
             .bind(min_lon)
@@ -146,14 +138,224 @@ impl EventContext {
         Ok(rows)
     }
 
-    // create, update, delete methods stay the same...
+    pub async fn find_by_series(&self, series_id: i64) -> Result<Vec<EventRow>, AppError> {
+        let query_str = format!(
+            "{} WHERE e.series_id = ? ORDER BY substr(e.start_date, 1, 10)",
+            EVENT_SELECT
+        );
+        let rows = sqlx::query_as::<_, EventRow>(&query_str)
+            .bind(series_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    /// The event as it was submitted, read back from event_data
+    pub async fn find_stored(&self, id: i64) -> Result<NomEvent, AppError> {
+        let (raw, series_id) = sqlx::query_as::<_, (String, Option<i64>)>(
+            "SELECT event_data, series_id FROM events WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let mut event: NomEvent = from_stored_json(StoredKind::Event, &raw)?;
+        event.id = Some(id);
+        event.series_id = series_id;
+        Ok(event)
+    }
+
+    ///The require_microevent_approval column alone, without reading event_data
+    pub async fn find_require_approval(&self, id: i64) -> Result<bool, AppError> {
+        let required = sqlx::query_scalar::<_, bool>(
+            "SELECT require_microevent_approval FROM events WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(required)
+    }
+
+    ///The time_zone column alone, None when the event has not set one
+    pub async fn find_time_zone(&self, id: i64) -> Result<Option<String>, AppError> {
+        let time_zone =
+            sqlx::query_scalar::<_, Option<String>>("SELECT time_zone FROM events WHERE id = ?")
+                .bind(id)
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(time_zone)
+    }
+
+    ///The only write to series_id after create, update() leaves it alone
+    pub async fn set_series(&self, id: i64, series_id: Option<i64>) -> Result<bool, AppError> {
+        let result = sqlx::query("UPDATE events SET series_id = ? WHERE id = ?")
+            .bind(series_id)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Combined search - every filter that is set on the params is ANDed together
+    pub async fn search(&self, params: &EventQueryParams) -> Result<Vec<EventRow>, AppError> {
+        let mut conditions: Vec<String> = Vec::new();
+
+        if params.event_type.is_some() {
+            conditions.push("e.event_type_id = ?".to_string());
+        }
+        if params.camping_allowed.is_some() {
+            conditions.push("e.camping_allowed = ?".to_string());
+        }
+
+        // Same bounding box approximation as find_nearby
+        let bounds = match (params.latitude, params.longitude, params.radius_miles) {
+            (Some(lat), Some(lon), Some(radius_miles)) => {
+                let lat_delta = radius_miles / 69.0;
+                let lon_delta = radius_miles / (69.0 * f64::cos(lat.to_radians()));
+                conditions.push(
+                    "e.latitude IS NOT NULL AND e.longitude IS NOT NULL
+                     AND e.latitude BETWEEN ? AND ? AND e.longitude BETWEEN ? AND ?"
+                        .to_string(),
+                );
+                Some((
+                    lat - lat_delta,
+                    lat + lat_delta,
+                    lon - lon_delta,
+                    lon + lon_delta,
+                ))
+            }
+            _ => None,
+        };
+
+        let bbox = params.bounding_box()?;
+        if bbox.is_some() {
+            conditions.push(
+                "e.latitude IS NOT NULL AND e.longitude IS NOT NULL
+                 AND e.longitude BETWEEN ? AND ? AND e.latitude BETWEEN ? AND ?"
+                    .to_string(),
+            );
+        }
+
+        // Columns on the detail tables, a missing detail row counts as false
+        let flags: Vec<(&str, bool)> = [
+            ("coalesce(am.showers, 0)", params.showers),
+            ("coalesce(am.potable_water, 0)", params.potable_water),
+            ("coalesce(am.wifi, 0)", params.wifi),
+            ("coalesce(ci.pet_friendly, 0)", params.pet_friendly),
+            ("coalesce(ci.fires_allowed, 0)", params.fires_allowed),
+            (
+                "coalesce(rv.hookups_available, 0)",
+                params.hookups_available,
+            ),
+            ("coalesce(rv.dump_station, 0)", params.dump_station),
+        ]
+        .into_iter()
+        .filter_map(|(column, value)| value.map(|v| (column, v)))
+        .collect();
+
+        for (column, _) in &flags {
+            conditions.push(format!("{} = ?", column));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let query_str = format!("{} {} ORDER BY e.name", EVENT_SELECT, where_clause);
+
+        // Bind in the same order the conditions were pushed
+        let mut query = sqlx::query_as::<_, EventRow>(&query_str);
+        if let Some(event_type) = params.event_type {
+            query = query.bind(event_type);
+        }
+        if let Some(camping_allowed) = params.camping_allowed {
+            query = query.bind(camping_allowed);
+        }
+        if let Some((min_lat, max_lat, min_lon, max_lon)) = bounds {
+            query = query
+                .bind(min_lat)
+                .bind(max_lat)
+                .bind(min_lon)
+                .bind(max_lon);
+        }
+        if let Some((min_lon, min_lat, max_lon, max_lat)) = bbox {
+            query = query
+                .bind(min_lon)
+                .bind(max_lon)
+                .bind(min_lat)
+                .bind(max_lat);
+        }
+        for (_, value) in flags {
+            query = query.bind(value);
+        }
+
+        let rows = query.fetch_all(&self.pool).await?;
+        Ok(rows)
+    }
+
+    // event_data is still written alongside the typed tables until nothing reads it
     pub async fn create(&self, event: &NomEvent) -> Result<i64, AppError> {
-        let event_json = serde_json::to_string(event)?;
+        let mut tx = self.pool.begin().await?;
+        let id = Self::insert_event(&mut tx, event).await?;
+        tx.commit().await?;
+
+        Ok(id)
+    }
+
+    ///Inserts all events and records them as created by owner, in one
+    ///transaction. The owner's user_event_data row must already exist.
+    pub async fn create_many(
+        &self,
+        events: &[NomEvent],
+        owner: &str,
+    ) -> Result<Vec<i64>, AppError> {
+        let mut tx = self.pool.begin().await?;
+        let mut ids = Vec::with_capacity(events.len());
+        for event in events {
+            ids.push(Self::insert_event(&mut tx, event).await?);
+        }
+
+        for id in &ids {
+            let result = sqlx::query(
+                "UPDATE user_event_data
+                 SET created_events = json_insert(coalesce(created_events, '[]'), '$[#]', ?)
+                 WHERE user_id = ?",
+            )
+            .bind(id)
+            .bind(owner)
+            .execute(&mut *tx)
+            .await?;
+            if result.rows_affected() == 0 {
+                return Err(AppError::NotFound(format!(
+                    "No user collection for {}",
+                    owner
+                )));
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(ids)
+    }
+
+    async fn insert_event(
+        tx: &mut Transaction<'_, Sqlite>,
+        event: &NomEvent,
+    ) -> Result<i64, AppError> {
+        let event_json = to_stored_json(event)?;
 
         let result = sqlx::query(
-            "INSERT INTO events (name, description, website, event_type_id, latitude, longitude, 
-             start_date, end_date, camping_allowed, event_data) 
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO events (name, description, website, event_type_id, latitude, longitude,
+             start_date, end_date, camping_allowed, address, venue_name, parking_info, single_day,
+             early_arrival_available, early_arrival_date, late_departure_available, series_id,
+             require_microevent_approval, time_zone, event_data, modified_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))",
         )
         .bind(&event.name)
         .bind(&event.description)
@@ -161,8 +363,18 @@ impl EventContext {
         .bind(event.event_type_id) // Changed: now uses event_type_id
         .bind(event.location_info.latitude)
         .bind(event.location_info.longitude)
-        .bind(event.date_info.start_date.map(|d| d.to_string()))
-        .bind(event.date_info.end_date.map(|d| d.to_string()))
+        .bind(
+            event
+                .date_info
+                .start_date
+                .map(|d| d.to_rfc3339_opts(SecondsFormat::Secs, true)),
+        )
+        .bind(
+            event
+                .date_info
+                .end_date
+                .map(|d| d.to_rfc3339_opts(SecondsFormat::Secs, true)),
+        )
         .bind(
             event
                 .camping_info
@@ -170,20 +382,38 @@ impl EventContext {
                 .map(|c| c.camping_allowed)
                 .unwrap_or(false),
         )
+        .bind(&event.location_info.address)
+        .bind(&event.location_info.venue_name)
+        .bind(&event.location_info.parking_info)
+        .bind(event.date_info.single_day)
+        .bind(event.date_info.early_arrival_available)
+        .bind(&event.date_info.early_arrival_date)
+        .bind(event.date_info.late_departure_available)
+        .bind(event.series_id)
+        .bind(event.require_microevent_approval)
+        .bind(&event.location_info.time_zone)
         .bind(&event_json)
-        .execute(&self.pool)
+        .execute(&mut **tx)
         .await?;
 
-        Ok(result.last_insert_rowid())
+        let id = result.last_insert_rowid();
+        Self::write_details(tx, id, event).await?;
+
+        Ok(id)
     }
 
     pub async fn update(&self, id: i64, event: &NomEvent) -> Result<bool, AppError> {
-        let event_json = serde_json::to_string(event)?;
+        let event_json = to_stored_json(event)?;
+        let mut tx = self.pool.begin().await?;
 
         let result = sqlx::query(
-            "UPDATE events SET name = ?, description = ?, website = ?, event_type_id = ?, 
-             latitude = ?, longitude = ?, start_date = ?, end_date = ?, camping_allowed = ?, 
-             event_data = ? WHERE id = ?",
+            "UPDATE events SET name = ?, description = ?, website = ?, event_type_id = ?,
+             latitude = ?, longitude = ?, start_date = ?, end_date = ?, camping_allowed = ?,
+             address = ?, venue_name = ?, parking_info = ?, single_day = ?,
+             early_arrival_available = ?, early_arrival_date = ?, late_departure_available = ?,
+             require_microevent_approval = ?, time_zone = ?, event_data = ?,
+             modified_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+             WHERE id = ?",
         )
         .bind(&event.name)
         .bind(&event.description)
@@ -191,8 +421,18 @@ impl EventContext {
         .bind(event.event_type_id) // Changed: now uses event_type_id
         .bind(event.location_info.latitude)
         .bind(event.location_info.longitude)
-        .bind(event.date_info.start_date.map(|d| d.to_string()))
-        .bind(event.date_info.end_date.map(|d| d.to_string()))
+        .bind(
+            event
+                .date_info
+                .start_date
+                .map(|d| d.to_rfc3339_opts(SecondsFormat::Secs, true)),
+        )
+        .bind(
+            event
+                .date_info
+                .end_date
+                .map(|d| d.to_rfc3339_opts(SecondsFormat::Secs, true)),
+        )
         .bind(
             event
                 .camping_info
@@ -200,12 +440,228 @@ impl EventContext {
                 .map(|c| c.camping_allowed)
                 .unwrap_or(false),
         )
+        .bind(&event.location_info.address)
+        .bind(&event.location_info.venue_name)
+        .bind(&event.location_info.parking_info)
+        .bind(event.date_info.single_day)
+        .bind(event.date_info.early_arrival_available)
+        .bind(&event.date_info.early_arrival_date)
+        .bind(event.date_info.late_departure_available)
+        .bind(event.require_microevent_approval)
+        .bind(&event.location_info.time_zone)
         .bind(&event_json)
         .bind(id)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
-        Ok(result.rows_affected() > 0)
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        Self::write_details(&mut tx, id, event).await?;
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Rewrites event_data, the columns read out of it and the detail tables, for the
+    /// schema upgrade. Leaves modified_at and series_id alone, an upgrade is not a
+    /// change anyone made.
+    pub async fn update_stored_data(&self, id: i64, event: &NomEvent) -> Result<bool, AppError> {
+        let event_json = to_stored_json(event)?;
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            "UPDATE events SET require_microevent_approval = ?, time_zone = ?, event_data = ?
+             WHERE id = ?",
+        )
+        .bind(event.require_microevent_approval)
+        .bind(&event.location_info.time_zone)
+        .bind(&event_json)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        Self::write_details(&mut tx, id, event).await?;
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Replaces the amenity / camping / rv / generator rows for an event
+    async fn write_details(
+        tx: &mut Transaction<'_, Sqlite>,
+        id: i64,
+        event: &NomEvent,
+    ) -> Result<(), AppError> {
+        for table in [
+            "event_amenities",
+            "event_camping_info",
+            "event_rv_options",
+            "event_generator_options",
+        ] {
+            sqlx::query(&format!("DELETE FROM {} WHERE event_id = ?", table))
+                .bind(id)
+                .execute(&mut **tx)
+                .await?;
+        }
+
+        if let Some(a) = &event.amenities {
+            sqlx::query(
+                "INSERT INTO event_amenities (event_id, bathrooms, showers, potable_water, wifi,
+                 cell_service_quality, firewood_available, ice_available, trash_service,
+                 recycling, laundry)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(a.bathrooms)
+            .bind(a.showers)
+            .bind(a.potable_water)
+            .bind(a.wifi)
+            .bind(&a.cell_service_quality)
+            .bind(a.firewood_available)
+            .bind(a.ice_available)
+            .bind(a.trash_service)
+            .bind(a.recycling)
+            .bind(a.laundry)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        let Some(c) = &event.camping_info else {
+            return Ok(());
+        };
+
+        sqlx::query(
+            "INSERT INTO event_camping_info (event_id, walking_distance, tent_camping,
+             campsite_reservations_required, primitive_camping, developed_campsites,
+             max_stay_nights, pet_friendly, quiet_hours, fires_allowed, van_camping,
+             car_camping, truck_camping, rooftop_tent_allowed)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(c.walking_distance)
+        .bind(c.tent_camping)
+        .bind(c.campsite_reservations_required)
+        .bind(c.primitive_camping)
+        .bind(c.developed_campsites)
+        .bind(c.max_stay_nights)
+        .bind(c.pet_friendly)
+        .bind(&c.quiet_hours)
+        .bind(c.fires_allowed)
+        .bind(c.vehicle_camping.van_camping)
+        .bind(c.vehicle_camping.car_camping)
+        .bind(c.vehicle_camping.truck_camping)
+        .bind(c.vehicle_camping.rooftop_tent_allowed)
+        .execute(&mut **tx)
+        .await?;
+
+        let rv = &c.rv_camping;
+        let hookups = rv.hookups_available.as_ref();
+        sqlx::query(
+            "INSERT INTO event_rv_options (event_id, rv_allowed, class_a_allowed, class_b_allowed,
+             class_c_allowed, travel_trailers_allowed, fifth_wheel_allowed, max_length_feet,
+             max_width_feet, hookups_available, hookup_electric, hookup_water, hookup_sewer,
+             hookup_amp_service, dump_station)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(rv.allowed)
+        .bind(rv.class_a_allowed)
+        .bind(rv.class_b_allowed)
+        .bind(rv.class_c_allowed)
+        .bind(rv.travel_trailers_allowed)
+        .bind(rv.fifth_wheel_allowed)
+        .bind(rv.max_length_feet)
+        .bind(rv.max_width_feet)
+        .bind(hookups.is_some())
+        .bind(hookups.map(|h| h.electric).unwrap_or(false))
+        .bind(hookups.map(|h| h.water).unwrap_or(false))
+        .bind(hookups.map(|h| h.sewer).unwrap_or(false))
+        .bind(hookups.and_then(|h| h.amp_service.clone()))
+        .bind(rv.dump_station)
+        .execute(&mut **tx)
+        .await?;
+
+        if let Some(g) = &c.generator_options {
+            let quiet = g.quiet_hours.as_ref();
+            sqlx::query(
+                "INSERT INTO event_generator_options (event_id, generators_allowed,
+                 max_decibel_limit, inverter_generators_only, propane_generators_allowed,
+                 gasoline_generators_allowed, diesel_generators_allowed,
+                 designated_generator_areas, distance_from_neighbors_feet,
+                 fuel_storage_restrictions, has_quiet_hours, quiet_hours_all_day,
+                 quiet_hours_start, quiet_hours_end, quiet_hours_days)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(g.generators_allowed)
+            .bind(g.max_decibel_limit)
+            .bind(g.inverter_generators_only)
+            .bind(g.propane_generators_allowed)
+            .bind(g.gasoline_generators_allowed)
+            .bind(g.diesel_generators_allowed)
+            .bind(g.designated_generator_areas)
+            .bind(g.distance_from_neighbors_feet)
+            .bind(&g.fuel_storage_restrictions)
+            .bind(quiet.is_some())
+            .bind(quiet.map(|q| q.all_day_restriction).unwrap_or(false))
+            .bind(quiet.and_then(|q| q.start_time.clone()))
+            .bind(quiet.and_then(|q| q.end_time.clone()))
+            .bind(quiet.and_then(|q| q.days_of_week.as_ref().map(|days| days.join(","))))
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// (id, name, start_date) of every event, for duplicate checks
+    pub async fn find_duplicate_keys(
+        &self,
+    ) -> Result<Vec<(i64, String, Option<String>)>, AppError> {
+        let rows = sqlx::query_as::<_, (i64, String, Option<String>)>(
+            "SELECT id, name, start_date FROM events",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Events created or updated at or after the timestamp (RFC 3339, UTC), with their
+    /// modified_at. Inclusive, since modified_at only has whole seconds.
+    pub async fn find_modified_since(&self, since: &str) -> Result<Vec<(i64, String)>, AppError> {
+        let rows = sqlx::query_as::<_, (i64, String)>(
+            "SELECT id, modified_at FROM events WHERE modified_at >= ?",
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn find_event_type_ids(&self) -> Result<Vec<i64>, AppError> {
+        let ids = sqlx::query_scalar::<_, i64>("SELECT id FROM event_types")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(ids)
+    }
+
+    /// Raw event_data for every event, used by the schema upgrade job
+    pub async fn find_all_event_data(&self) -> Result<Vec<(i64, String)>, AppError> {
+        let rows =
+            sqlx::query_as::<_, (i64, String)>("SELECT id, event_data FROM events ORDER BY id")
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(rows)
     }
 
     pub async fn delete(&self, id: i64) -> Result<bool, AppError> {
@@ -0,0 +1,99 @@
+// ============================================================================
+// Repository: src/context/event_series_context.rs
+// ============================================================================
+
+use crate::errors::AppError;
+use crate::models::database_models::EventSeriesRow;
+use crate::models::event_models::EventSeries;
+use sqlx::SqlitePool;
+
+pub struct EventSeriesContext {
+    pool: SqlitePool,
+}
+
+impl EventSeriesContext {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_all(&self) -> Result<Vec<EventSeries>, AppError> {
+        let rows = sqlx::query_as::<_, EventSeriesRow>(
+            "SELECT id, name, description, website FROM event_series ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Self::to_series).collect())
+    }
+
+    pub async fn find_by_id(&self, id: i64) -> Result<EventSeries, AppError> {
+        let row = sqlx::query_as::<_, EventSeriesRow>(
+            "SELECT id, name, description, website FROM event_series WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(Self::to_series(row))
+    }
+
+    pub async fn get_by_id_list(&self, input: Vec<i64>) -> Result<Vec<EventSeries>, AppError> {
+        let placeholders = input.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        let query_str = format!(
+            "SELECT id, name, description, website FROM event_series WHERE id IN ({}) ORDER BY name",
+            placeholders
+        );
+
+        let mut query = sqlx::query_as::<_, EventSeriesRow>(&query_str);
+        for id in input {
+            query = query.bind(id);
+        }
+
+        let rows = query.fetch_all(&self.pool).await?;
+        Ok(rows.into_iter().map(Self::to_series).collect())
+    }
+
+    pub async fn create(&self, series: &EventSeries) -> Result<i64, AppError> {
+        let result =
+            sqlx::query("INSERT INTO event_series (name, description, website) VALUES (?, ?, ?)")
+                .bind(&series.name)
+                .bind(&series.description)
+                .bind(&series.website)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    pub async fn update(&self, id: i64, series: &EventSeries) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE event_series SET name = ?, description = ?, website = ? WHERE id = ?",
+        )
+        .bind(&series.name)
+        .bind(&series.description)
+        .bind(&series.website)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete(&self, id: i64) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM event_series WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn to_series(row: EventSeriesRow) -> EventSeries {
+        EventSeries {
+            id: Some(row.id),
+            name: row.name,
+            description: row.description,
+            website: row.website,
+        }
+    }
+}
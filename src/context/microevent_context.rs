@@ -1,10 +1,20 @@
 // src/context/event_context.rs
 
 use crate::errors::AppError;
-use crate::models::microevents_models::Microevent;
-use chrono::Utc;
+use crate::models::microevents_models::{ApprovalStatus, Microevent};
+use chrono::{DateTime, Utc};
 use sqlx::SqlitePool;
 
+const MICROEVENT_COLUMNS: &str = "id, event_id, user_id, name, archive, description,
+     start_time, end_time, area_id, track_id, recurrence_rule, recurrence_parent_id,
+     occurrence_date, detached, cancelled, capacity, approval_status, review_note,
+     created_at, updated_at,
+     (SELECT COUNT(*) FROM microevent_rsvps r
+      WHERE r.microevent_id = microevents.id AND r.status = 'going') AS attendee_count,
+     CASE WHEN capacity IS NULL THEN NULL ELSE MAX(capacity - (
+         SELECT COUNT(*) FROM microevent_rsvps r
+         WHERE r.microevent_id = microevents.id AND r.status = 'going'), 0) END AS seats_remaining";
+
 pub struct MicroeventContext {
     pool: SqlitePool,
 }
@@ -16,12 +26,10 @@ impl MicroeventContext {
     }
 
     pub async fn find_all(&self) -> Result<Vec<Microevent>, AppError> {
-        let rows = sqlx::query_as::<_, Microevent>(
-            "SELECT id, event_id, user_id, name, archive, description, 
-             start_time, end_time, created_at, updated_at
-             FROM microevents
-             ORDER BY start_time",
-        )
+        let rows = sqlx::query_as::<_, Microevent>(&format!(
+            "SELECT {} FROM microevents ORDER BY start_time",
+            MICROEVENT_COLUMNS
+        ))
         .fetch_all(&self.pool)
         .await?;
 
@@ -29,12 +37,10 @@ impl MicroeventContext {
     }
 
     pub async fn find_by_id(&self, id: i64) -> Result<Microevent, AppError> {
-        let row = sqlx::query_as::<_, Microevent>(
-            "SELECT id, event_id, user_id, name, archive, description,
-             start_time, end_time, created_at, updated_at
-             FROM microevents
-             WHERE id = ?",
-        )
+        let row = sqlx::query_as::<_, Microevent>(&format!(
+            "SELECT {} FROM microevents WHERE id = ?",
+            MICROEVENT_COLUMNS
+        ))
         .bind(id)
         .fetch_one(&self.pool)
         .await?;
@@ -47,12 +53,8 @@ impl MicroeventContext {
         let placeholders = input.iter().map(|_| "?").collect::<Vec<_>>().join(",");
 
         let query_str = format!(
-            "SELECT 
-            e.id, e.event_id, e.user_id, e.name,  e.archive, e.description,
-             e.start_time, e.end_time, e.created_at, e.updated_at
-         FROM microevents e         
-         WHERE e.id IN ({})",
-            placeholders
+            "SELECT {} FROM microevents WHERE id IN ({})",
+            MICROEVENT_COLUMNS, placeholders
         );
 
         let mut query = sqlx::query_as::<_, Microevent>(&query_str);
@@ -67,13 +69,10 @@ impl MicroeventContext {
     }
 
     pub async fn find_by_event(&self, event_id: i64) -> Result<Vec<Microevent>, AppError> {
-        let rows = sqlx::query_as::<_, Microevent>(
-            "SELECT id, event_id, user_id, name, archive, description,
-             start_time, end_time, created_at, updated_at
-             FROM microevents
-             WHERE event_id = ?
-             ORDER BY start_time",
-        )
+        let rows = sqlx::query_as::<_, Microevent>(&format!(
+            "SELECT {} FROM microevents WHERE event_id = ? ORDER BY start_time",
+            MICROEVENT_COLUMNS
+        ))
         .bind(event_id)
         .fetch_all(&self.pool)
         .await?;
@@ -81,14 +80,35 @@ impl MicroeventContext {
         Ok(rows)
     }
 
+    ///What is still to come on the schedule: approved, not archived or
+    ///cancelled, and not over yet. Ones without an end are over once started.
+    pub async fn find_unfinished(
+        &self,
+        event_id: i64,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Microevent>, AppError> {
+        let rows = sqlx::query_as::<_, Microevent>(&format!(
+            "SELECT {} FROM microevents
+             WHERE event_id = ?1 AND archive = false AND cancelled = false
+               AND approval_status = 'approved' AND start_time IS NOT NULL
+               AND (datetime(end_time) > datetime(?2)
+                    OR (end_time IS NULL AND datetime(start_time) > datetime(?2)))
+             ORDER BY datetime(start_time), id",
+            MICROEVENT_COLUMNS
+        ))
+        .bind(event_id)
+        .bind(now.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
     pub async fn find_by_user(&self, user_id: i64) -> Result<Vec<Microevent>, AppError> {
-        let rows = sqlx::query_as::<_, Microevent>(
-            "SELECT id, event_id, user_id, name, archive, description,
-             start_time, end_time, created_at, updated_at
-             FROM microevents
-             WHERE user_id = ?
-             ORDER BY start_time",
-        )
+        let rows = sqlx::query_as::<_, Microevent>(&format!(
+            "SELECT {} FROM microevents WHERE user_id = ? ORDER BY start_time",
+            MICROEVENT_COLUMNS
+        ))
         .bind(user_id)
         .fetch_all(&self.pool)
         .await?;
@@ -99,8 +119,10 @@ impl MicroeventContext {
     pub async fn create(&self, microevent: &Microevent) -> Result<i64, AppError> {
         let result = sqlx::query(
             "INSERT INTO microevents (event_id, user_id, name, archive, description, 
-             start_time, end_time, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+             start_time, end_time, area_id, track_id, recurrence_rule, recurrence_parent_id,
+             occurrence_date, detached, cancelled, capacity, approval_status, review_note,
+             created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(microevent.event_id)
         .bind(&microevent.user_id)
@@ -109,6 +131,20 @@ impl MicroeventContext {
         .bind(&microevent.description)
         .bind(microevent.start_time.map(|dt| dt.to_rfc3339()))
         .bind(microevent.end_time.map(|dt| dt.to_rfc3339()))
+        .bind(microevent.area_id)
+        .bind(microevent.track_id)
+        .bind(&microevent.recurrence_rule)
+        .bind(microevent.recurrence_parent_id)
+        .bind(
+            microevent
+                .occurrence_date
+                .map(|d| d.format("%Y-%m-%d").to_string()),
+        )
+        .bind(microevent.detached)
+        .bind(microevent.cancelled)
+        .bind(microevent.capacity)
+        .bind(microevent.approval_status)
+        .bind(&microevent.review_note)
         .bind(Utc::now().to_rfc3339())
         .bind(Utc::now().to_rfc3339())
         .execute(&self.pool)
@@ -121,7 +157,9 @@ impl MicroeventContext {
         let result = sqlx::query(
             "UPDATE microevents 
              SET event_id = ?, user_id = ?, name = ?, archive = ?, description = ?,
-                 start_time = ?, end_time = ?, updated_at = ?
+                 start_time = ?, end_time = ?, area_id = ?, track_id = ?,
+                 recurrence_rule = ?, recurrence_parent_id = ?, occurrence_date = ?,
+                 detached = ?, cancelled = ?, capacity = ?, updated_at = ?
              WHERE id = ?",
         )
         .bind(microevent.event_id)
@@ -131,6 +169,18 @@ impl MicroeventContext {
         .bind(&microevent.description)
         .bind(microevent.start_time.map(|dt| dt.to_rfc3339()))
         .bind(microevent.end_time.map(|dt| dt.to_rfc3339()))
+        .bind(microevent.area_id)
+        .bind(microevent.track_id)
+        .bind(&microevent.recurrence_rule)
+        .bind(microevent.recurrence_parent_id)
+        .bind(
+            microevent
+                .occurrence_date
+                .map(|d| d.format("%Y-%m-%d").to_string()),
+        )
+        .bind(microevent.detached)
+        .bind(microevent.cancelled)
+        .bind(microevent.capacity)
         .bind(Utc::now().to_rfc3339())
         .bind(id)
         .execute(&self.pool)
@@ -170,14 +220,56 @@ impl MicroeventContext {
         Ok(result.rows_affected() > 0)
     }
 
-    pub async fn find_active(&self) -> Result<Vec<Microevent>, AppError> {
-        let rows = sqlx::query_as::<_, Microevent>(
-            "SELECT id, event_id, user_id, name, archive, description,
-             start_time, end_time, created_at, updated_at
-             FROM microevents
-             WHERE archive = false
-             ORDER BY start_time",
+    ///The review outcome is only ever written here, update() leaves it alone
+    pub async fn set_approval(
+        &self,
+        id: i64,
+        status: ApprovalStatus,
+        note: Option<&str>,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE microevents SET approval_status = ?, review_note = ?, updated_at = ? WHERE id = ?",
         )
+        .bind(status)
+        .bind(note)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    ///The other occurrences of a recurring microevent, by date
+    pub async fn find_occurrences(&self, parent_id: i64) -> Result<Vec<Microevent>, AppError> {
+        let rows = sqlx::query_as::<_, Microevent>(&format!(
+            "SELECT {} FROM microevents WHERE recurrence_parent_id = ? ORDER BY occurrence_date, id",
+            MICROEVENT_COLUMNS
+        ))
+        .bind(parent_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn set_cancelled(&self, id: i64, cancelled: bool) -> Result<bool, AppError> {
+        let result =
+            sqlx::query("UPDATE microevents SET cancelled = ?, updated_at = ? WHERE id = ?")
+                .bind(cancelled)
+                .bind(Utc::now().to_rfc3339())
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn find_active(&self) -> Result<Vec<Microevent>, AppError> {
+        let rows = sqlx::query_as::<_, Microevent>(&format!(
+            "SELECT {} FROM microevents WHERE archive = false ORDER BY start_time",
+            MICROEVENT_COLUMNS
+        ))
         .fetch_all(&self.pool)
         .await?;
 
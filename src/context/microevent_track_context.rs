@@ -0,0 +1,98 @@
+// ============================================================================
+// Repository: src/context/microevent_track_context.rs
+// ============================================================================
+
+use crate::errors::AppError;
+use crate::models::microevents_models::{MicroeventTrack, MicroeventTrackRequest};
+use sqlx::SqlitePool;
+
+const TRACK_COLUMNS: &str = "id, event_id, name, description, color, sort_order";
+
+pub struct MicroeventTrackContext {
+    pool: SqlitePool,
+}
+
+impl MicroeventTrackContext {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_by_event(&self, event_id: i64) -> Result<Vec<MicroeventTrack>, AppError> {
+        let query = format!(
+            "SELECT {} FROM microevent_tracks WHERE event_id = ? ORDER BY sort_order, name, id",
+            TRACK_COLUMNS
+        );
+        let rows = sqlx::query_as::<_, MicroeventTrack>(&query)
+            .bind(event_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn find_by_id(&self, id: i64) -> Result<MicroeventTrack, AppError> {
+        let query = format!(
+            "SELECT {} FROM microevent_tracks WHERE id = ?",
+            TRACK_COLUMNS
+        );
+        let row = sqlx::query_as::<_, MicroeventTrack>(&query)
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row)
+    }
+
+    pub async fn create(
+        &self,
+        event_id: i64,
+        track: &MicroeventTrackRequest,
+    ) -> Result<i64, AppError> {
+        let result = sqlx::query(
+            "INSERT INTO microevent_tracks (event_id, name, description, color, sort_order)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(event_id)
+        .bind(track.name.trim())
+        .bind(&track.description)
+        .bind(&track.color)
+        .bind(track.sort_order)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    pub async fn update(&self, id: i64, track: &MicroeventTrackRequest) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE microevent_tracks SET name = ?, description = ?, color = ?, sort_order = ?
+             WHERE id = ?",
+        )
+        .bind(track.name.trim())
+        .bind(&track.description)
+        .bind(&track.color)
+        .bind(track.sort_order)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    ///Microevents on the track stay, without a track
+    pub async fn delete(&self, id: i64) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("UPDATE microevents SET track_id = NULL WHERE track_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query("DELETE FROM microevent_tracks WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -12,3 +12,23 @@ pub mod analytics_context;
 pub use analytics_context::AnalyticsContext;
 pub mod user_collection_context;
 pub use user_collection_context::UserCollectionContext;
+pub mod event_series_context;
+pub use event_series_context::EventSeriesContext;
+pub mod calendar_token_context;
+pub use calendar_token_context::CalendarTokenContext;
+pub mod saved_search_context;
+pub use saved_search_context::SavedSearchContext;
+pub mod notification_context;
+pub use notification_context::NotificationContext;
+pub mod email_outbox_context;
+pub use email_outbox_context::EmailOutboxContext;
+pub mod email_verification_context;
+pub use email_verification_context::EmailVerificationContext;
+pub mod webhook_context;
+pub use webhook_context::WebhookContext;
+pub mod venue_area_context;
+pub use venue_area_context::VenueAreaContext;
+pub mod microevent_track_context;
+pub use microevent_track_context::MicroeventTrackContext;
+pub mod rsvp_context;
+pub use rsvp_context::RsvpContext;
@@ -0,0 +1,197 @@
+// ============================================================================
+// Repository: src/context/notification_context.rs
+// ============================================================================
+
+use crate::context::EmailOutboxContext;
+use crate::errors::AppError;
+use crate::models::database_models::NotificationRow;
+use crate::models::email_models::QueuedEmail;
+use sqlx::SqlitePool;
+
+pub struct NotificationContext {
+    pool: SqlitePool,
+}
+
+impl NotificationContext {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// The email, if any, goes into the outbox in the same transaction
+    pub async fn create(
+        &self,
+        user_id: &str,
+        category: &str,
+        title: &str,
+        body: &str,
+        event_id: Option<i64>,
+        email: Option<&QueuedEmail>,
+    ) -> Result<i64, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            "INSERT INTO notifications (user_id, category, title, body, event_id)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(user_id)
+        .bind(category)
+        .bind(title)
+        .bind(body)
+        .bind(event_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if let Some(email) = email {
+            EmailOutboxContext::insert(&mut tx, email).await?;
+        }
+        tx.commit().await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// UserRow.notification_preferences, raw JSON
+    pub async fn find_preferences(&self, user_id: &str) -> Result<Option<String>, AppError> {
+        let preferences = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT notification_preferences FROM users WHERE id = ?",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(preferences.flatten())
+    }
+
+    pub async fn find_by_user(
+        &self,
+        user_id: &str,
+        unread_only: bool,
+        limit: i64,
+    ) -> Result<Vec<NotificationRow>, AppError> {
+        let rows = sqlx::query_as::<_, NotificationRow>(
+            "SELECT id, user_id, category, title, body, event_id, created_at, read_at
+             FROM notifications
+             WHERE user_id = ? AND (? = 0 OR read_at IS NULL)
+             ORDER BY created_at DESC, id DESC
+             LIMIT ?",
+        )
+        .bind(user_id)
+        .bind(unread_only)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn count_unread(&self, user_id: &str) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM notifications WHERE user_id = ? AND read_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// False when the notification does not exist or belongs to someone else
+    pub async fn mark_read(&self, id: i64, user_id: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE notifications SET read_at = coalesce(read_at, CURRENT_TIMESTAMP)
+             WHERE id = ? AND user_id = ?",
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn mark_all_read(&self, user_id: &str) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            "UPDATE notifications SET read_at = CURRENT_TIMESTAMP
+             WHERE user_id = ? AND read_at IS NULL",
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn update_preferences(
+        &self,
+        user_id: &str,
+        preferences: &str,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query("UPDATE users SET notification_preferences = ? WHERE id = ?")
+            .bind(preferences)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Users with the event in their favorites
+    pub async fn find_users_favoriting(&self, event_id: i64) -> Result<Vec<String>, AppError> {
+        let user_ids = sqlx::query_scalar::<_, String>(
+            "SELECT user_id FROM user_event_data
+             WHERE EXISTS (SELECT 1 FROM json_each(favorite_events) WHERE value = ?)",
+        )
+        .bind(event_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(user_ids)
+    }
+
+    /// (user_name, email, email_verified)
+    pub async fn find_contact(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<(String, Option<String>, bool)>, AppError> {
+        let contact = sqlx::query_as::<_, (String, Option<String>, bool)>(
+            "SELECT user_name, email, email_verified FROM users WHERE id = ?",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(contact)
+    }
+
+    pub async fn find_users_with_unread(&self) -> Result<Vec<String>, AppError> {
+        let user_ids = sqlx::query_scalar::<_, String>(
+            "SELECT DISTINCT user_id FROM notifications WHERE read_at IS NULL",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(user_ids)
+    }
+
+    /// Unread notifications created after `since` (all of them when None)
+    pub async fn find_unread_since(
+        &self,
+        user_id: &str,
+        since: Option<&str>,
+    ) -> Result<Vec<NotificationRow>, AppError> {
+        // datetime() on both sides, the columns mix CURRENT_TIMESTAMP and RFC 3339
+        let rows = sqlx::query_as::<_, NotificationRow>(
+            "SELECT id, user_id, category, title, body, event_id, created_at, read_at
+             FROM notifications
+             WHERE user_id = ? AND read_at IS NULL
+               AND (? IS NULL OR datetime(created_at) > datetime(?))
+             ORDER BY created_at, id",
+        )
+        .bind(user_id)
+        .bind(since)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+}
@@ -0,0 +1,177 @@
+// ============================================================================
+// Repository: src/context/rsvp_context.rs
+// ============================================================================
+// Every seat decision is made inside a single write statement (or a
+// transaction that opens with one), so SQLite's write lock serializes
+// concurrent RSVPs and nobody is handed a seat that isn't there.
+
+use crate::errors::AppError;
+use sqlx::SqlitePool;
+
+// Free seats on ?1 as a LIMIT, -1 (no limit) when capacity is unlimited
+const OPEN_SEATS: &str = "(SELECT COALESCE(CASE WHEN m.capacity IS NULL THEN -1
+         ELSE MAX(m.capacity - (SELECT COUNT(*) FROM microevent_rsvps r
+                                WHERE r.microevent_id = m.id AND r.status = 'going'), 0) END, 0)
+     FROM microevents m WHERE m.id = ?1)";
+
+pub struct RsvpContext {
+    pool: SqlitePool,
+}
+
+impl RsvpContext {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    ///Takes a seat if one is open, otherwise a place on the waitlist.
+    ///None when the user already has an RSVP.
+    pub async fn create(
+        &self,
+        microevent_id: i64,
+        user_id: &str,
+    ) -> Result<Option<String>, AppError> {
+        let status = sqlx::query_scalar::<_, String>(
+            "INSERT INTO microevent_rsvps (microevent_id, user_id, status)
+             SELECT m.id, ?2,
+                    CASE WHEN m.capacity IS NULL
+                           OR (SELECT COUNT(*) FROM microevent_rsvps r
+                               WHERE r.microevent_id = m.id AND r.status = 'going') < m.capacity
+                         THEN 'going' ELSE 'waitlisted' END
+             FROM microevents m
+             WHERE m.id = ?1
+             ON CONFLICT (microevent_id, user_id) DO NOTHING
+             RETURNING status",
+        )
+        .bind(microevent_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(status)
+    }
+
+    ///(id, status) of the user's RSVP
+    pub async fn find(
+        &self,
+        microevent_id: i64,
+        user_id: &str,
+    ) -> Result<Option<(i64, String)>, AppError> {
+        let rsvp = sqlx::query_as::<_, (i64, String)>(
+            "SELECT id, status FROM microevent_rsvps WHERE microevent_id = ? AND user_id = ?",
+        )
+        .bind(microevent_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(rsvp)
+    }
+
+    ///1 for the next in line
+    pub async fn waitlist_position(
+        &self,
+        microevent_id: i64,
+        rsvp_id: i64,
+    ) -> Result<i64, AppError> {
+        let position = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM microevent_rsvps
+             WHERE microevent_id = ? AND status = 'waitlisted' AND id <= ?",
+        )
+        .bind(microevent_id)
+        .bind(rsvp_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(position)
+    }
+
+    ///Drops the RSVP and hands a freed seat to the waitlist in the same
+    ///transaction. None when there was no RSVP, otherwise the promoted user ids.
+    pub async fn delete(
+        &self,
+        microevent_id: i64,
+        user_id: &str,
+    ) -> Result<Option<Vec<String>>, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // a write first, so the transaction holds the write lock from here on
+        let removed = sqlx::query_scalar::<_, String>(
+            "DELETE FROM microevent_rsvps WHERE microevent_id = ? AND user_id = ? RETURNING status",
+        )
+        .bind(microevent_id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let promoted = match removed.as_deref() {
+            None => return Ok(None),
+            Some("going") => {
+                sqlx::query_scalar::<_, String>(&promote_query())
+                    .bind(microevent_id)
+                    .fetch_all(&mut *tx)
+                    .await?
+            }
+            Some(_) => Vec::new(),
+        };
+
+        tx.commit().await?;
+        Ok(Some(promoted))
+    }
+
+    ///Moves as many of the waitlist into open seats as fit, after capacity
+    ///went up. Returns the promoted user ids.
+    pub async fn promote(&self, microevent_id: i64) -> Result<Vec<String>, AppError> {
+        let promoted = sqlx::query_scalar::<_, String>(&promote_query())
+            .bind(microevent_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(promoted)
+    }
+
+    ///Microevents the user has a seat at
+    pub async fn find_going_ids(&self, user_id: &str) -> Result<Vec<i64>, AppError> {
+        let ids = sqlx::query_scalar::<_, i64>(
+            "SELECT microevent_id FROM microevent_rsvps WHERE user_id = ? AND status = 'going'",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    ///(user_id, user_name, status, created_at), seats first, both in RSVP order
+    pub async fn find_attendees(
+        &self,
+        microevent_id: i64,
+    ) -> Result<Vec<(String, Option<String>, String, String)>, AppError> {
+        let rows = sqlx::query_as::<_, (String, Option<String>, String, String)>(
+            "SELECT r.user_id, u.user_name, r.status, r.created_at
+             FROM microevent_rsvps r
+             LEFT JOIN users u ON u.id = r.user_id
+             WHERE r.microevent_id = ?
+             ORDER BY r.status = 'waitlisted', r.id",
+        )
+        .bind(microevent_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+}
+
+fn promote_query() -> String {
+    format!(
+        "UPDATE microevent_rsvps
+         SET status = 'going', promoted_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+         WHERE id IN (
+             SELECT id FROM microevent_rsvps
+             WHERE microevent_id = ?1 AND status = 'waitlisted'
+             ORDER BY id
+             LIMIT {}
+         )
+         RETURNING user_id",
+        OPEN_SEATS
+    )
+}
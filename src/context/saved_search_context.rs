@@ -0,0 +1,127 @@
+// ============================================================================
+// Repository: src/context/saved_search_context.rs
+// ============================================================================
+
+use crate::errors::AppError;
+use crate::models::database_models::SavedSearchRow;
+use crate::models::saved_search_models::SavedSearch;
+use sqlx::SqlitePool;
+
+pub struct SavedSearchContext {
+    pool: SqlitePool,
+}
+
+impl SavedSearchContext {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_by_user(&self, user_id: &str) -> Result<Vec<SavedSearchRow>, AppError> {
+        let rows = sqlx::query_as::<_, SavedSearchRow>(
+            "SELECT id, user_id, name, query, alerts_enabled, last_checked_at, checked_event_ids,
+                    created_at
+             FROM saved_searches WHERE user_id = ? ORDER BY name",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn find_by_id(&self, id: i64) -> Result<SavedSearchRow, AppError> {
+        let row = sqlx::query_as::<_, SavedSearchRow>(
+            "SELECT id, user_id, name, query, alerts_enabled, last_checked_at, checked_event_ids,
+                    created_at
+             FROM saved_searches WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    pub async fn find_alerting(&self) -> Result<Vec<SavedSearchRow>, AppError> {
+        let rows = sqlx::query_as::<_, SavedSearchRow>(
+            "SELECT id, user_id, name, query, alerts_enabled, last_checked_at, checked_event_ids,
+                    created_at
+             FROM saved_searches WHERE alerts_enabled = 1",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// New searches only alert on events changed from now on
+    pub async fn create(&self, user_id: &str, search: &SavedSearch) -> Result<i64, AppError> {
+        let query_json = serde_json::to_string(&search.query)?;
+
+        let result = sqlx::query(
+            "INSERT INTO saved_searches (user_id, name, query, alerts_enabled, last_checked_at)
+             VALUES (?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))",
+        )
+        .bind(user_id)
+        .bind(&search.name)
+        .bind(&query_json)
+        .bind(search.alerts_enabled)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    pub async fn update(
+        &self,
+        id: i64,
+        user_id: &str,
+        search: &SavedSearch,
+    ) -> Result<bool, AppError> {
+        let query_json = serde_json::to_string(&search.query)?;
+
+        let result = sqlx::query(
+            "UPDATE saved_searches SET name = ?, query = ?, alerts_enabled = ?
+             WHERE id = ? AND user_id = ?",
+        )
+        .bind(&search.name)
+        .bind(&query_json)
+        .bind(search.alerts_enabled)
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    ///Moves the alert cursor, event_ids are the events modified exactly at checked_at
+    ///that this pass already handled
+    pub async fn set_last_checked(
+        &self,
+        id: i64,
+        checked_at: &str,
+        event_ids: &[i64],
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE saved_searches SET last_checked_at = ?, checked_event_ids = ? WHERE id = ?",
+        )
+        .bind(checked_at)
+        .bind(serde_json::to_string(event_ids)?)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: i64, user_id: &str) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM saved_searches WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -26,7 +26,8 @@ impl UserCollectionContext {
             saved_events,
             saved_microevents,
             created_events,
-            created_microevents
+            created_microevents,
+            favorite_series
         FROM user_event_data
         WHERE user_id = ?
         "#,
@@ -74,6 +75,12 @@ impl UserCollectionContext {
                         .as_deref()
                         .unwrap_or("[]"),
                 )?,
+
+                favorite_series: serde_json::from_str::<Vec<i64>>(
+                    row.get::<Option<String>, _>("favorite_series")
+                        .as_deref()
+                        .unwrap_or("[]"),
+                )?,
             });
         }
 
@@ -90,6 +97,7 @@ impl UserCollectionContext {
             saved_microevents: vec![],
             created_events: vec![],
             created_microevents: vec![],
+            favorite_series: vec![],
         };
 
         self.create(&empty).await?;
@@ -109,7 +117,8 @@ impl UserCollectionContext {
             saved_events,
             saved_microevents,
             created_events,
-            created_microevents
+            created_microevents,
+            favorite_series
         FROM user_event_data
         WHERE id = ?
         "#,
@@ -157,6 +166,12 @@ impl UserCollectionContext {
                         .as_deref()
                         .unwrap_or("[]"),
                 )?,
+
+                favorite_series: serde_json::from_str::<Vec<i64>>(
+                    row.get::<Option<String>, _>("favorite_series")
+                        .as_deref()
+                        .unwrap_or("[]"),
+                )?,
             });
         }
         Err(AppError::NotFound("User event data not found".to_string()))
@@ -189,13 +204,14 @@ impl UserCollectionContext {
         let saved_microevents = serde_json::to_string(&data.saved_microevents)?;
         let created_events = serde_json::to_string(&data.created_events)?;
         let created_microevents = serde_json::to_string(&data.created_microevents)?;
+        let favorite_series = serde_json::to_string(&data.favorite_series)?;
 
         sqlx::query(
             r#"
             INSERT INTO user_event_data 
             (user_id, favorite_events, favorite_microevents, saved_events, 
-             saved_microevents, created_events, created_microevents)
-            VALUES (?, ?, ?, ?, ?, ?, ?)
+             saved_microevents, created_events, created_microevents, favorite_series)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&data.user_id)
@@ -205,6 +221,7 @@ impl UserCollectionContext {
         .bind(&saved_microevents)
         .bind(&created_events)
         .bind(&created_microevents)
+        .bind(&favorite_series)
         .execute(&self.pool)
         .await?;
 
@@ -218,6 +235,7 @@ impl UserCollectionContext {
         let saved_microevents = serde_json::to_string(&data.saved_microevents)?;
         let created_events = serde_json::to_string(&data.created_events)?;
         let created_microevents = serde_json::to_string(&data.created_microevents)?;
+        let favorite_series = serde_json::to_string(&data.favorite_series)?;
 
         sqlx::query(
             r#"
@@ -227,7 +245,8 @@ impl UserCollectionContext {
                 saved_events = ?,
                 saved_microevents = ?,
                 created_events = ?,
-                created_microevents = ?
+                created_microevents = ?,
+                favorite_series = ?
             WHERE user_id = ?
             "#,
         )
@@ -237,6 +256,7 @@ impl UserCollectionContext {
         .bind(&saved_microevents)
         .bind(&created_events)
         .bind(&created_microevents)
+        .bind(&favorite_series)
         .bind(&data.user_id)
         .execute(&self.pool)
         .await?;
@@ -0,0 +1,114 @@
+// ============================================================================
+// Repository: src/context/venue_area_context.rs
+// ============================================================================
+
+use crate::errors::AppError;
+use crate::models::database_models::VenueAreaRow;
+use crate::models::venue_models::VenueAreaRequest;
+use sqlx::SqlitePool;
+
+const AREA_COLUMNS: &str =
+    "id, event_id, name, kind, description, latitude, longitude, polygon, sort_order, created_at";
+
+pub struct VenueAreaContext {
+    pool: SqlitePool,
+}
+
+impl VenueAreaContext {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    ///In grid column order
+    pub async fn find_by_event(&self, event_id: i64) -> Result<Vec<VenueAreaRow>, AppError> {
+        let query = format!(
+            "SELECT {} FROM venue_areas WHERE event_id = ? ORDER BY sort_order, name, id",
+            AREA_COLUMNS
+        );
+        let rows = sqlx::query_as::<_, VenueAreaRow>(&query)
+            .bind(event_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn find_by_id(&self, id: i64) -> Result<VenueAreaRow, AppError> {
+        let query = format!("SELECT {} FROM venue_areas WHERE id = ?", AREA_COLUMNS);
+        let row = sqlx::query_as::<_, VenueAreaRow>(&query)
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row)
+    }
+
+    ///polygon is the area's outline already serialized to JSON
+    pub async fn create(
+        &self,
+        event_id: i64,
+        area: &VenueAreaRequest,
+        polygon: Option<&str>,
+    ) -> Result<i64, AppError> {
+        let result = sqlx::query(
+            "INSERT INTO venue_areas
+             (event_id, name, kind, description, latitude, longitude, polygon, sort_order)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(event_id)
+        .bind(area.name.trim())
+        .bind(area.kind.as_str())
+        .bind(&area.description)
+        .bind(area.latitude)
+        .bind(area.longitude)
+        .bind(polygon)
+        .bind(area.sort_order)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    pub async fn update(
+        &self,
+        id: i64,
+        area: &VenueAreaRequest,
+        polygon: Option<&str>,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE venue_areas
+             SET name = ?, kind = ?, description = ?, latitude = ?, longitude = ?,
+                 polygon = ?, sort_order = ?
+             WHERE id = ?",
+        )
+        .bind(area.name.trim())
+        .bind(area.kind.as_str())
+        .bind(&area.description)
+        .bind(area.latitude)
+        .bind(area.longitude)
+        .bind(polygon)
+        .bind(area.sort_order)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    ///Microevents in the area stay on the schedule, unassigned
+    pub async fn delete(&self, id: i64) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("UPDATE microevents SET area_id = NULL WHERE area_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query("DELETE FROM venue_areas WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
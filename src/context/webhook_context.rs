@@ -0,0 +1,241 @@
+// ============================================================================
+// Repository: src/context/webhook_context.rs
+// ============================================================================
+
+use crate::errors::AppError;
+use crate::models::database_models::{WebhookDeliveryRow, WebhookRow};
+use sqlx::SqlitePool;
+
+const WEBHOOK_COLUMNS: &str = "id, owner_user_id, scope, url, secret, events, active, created_at";
+const DELIVERY_COLUMNS: &str = "id, webhook_id, event_type, payload, status, attempts, \
+     response_status, last_error, created_at, delivered_at";
+
+pub struct WebhookContext {
+    pool: SqlitePool,
+}
+
+impl WebhookContext {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_all(&self) -> Result<Vec<WebhookRow>, AppError> {
+        let query = format!("SELECT {} FROM webhooks ORDER BY id", WEBHOOK_COLUMNS);
+        let rows = sqlx::query_as::<_, WebhookRow>(&query)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn find_by_owner(&self, owner_user_id: &str) -> Result<Vec<WebhookRow>, AppError> {
+        let query = format!(
+            "SELECT {} FROM webhooks WHERE owner_user_id = ? ORDER BY id",
+            WEBHOOK_COLUMNS
+        );
+        let rows = sqlx::query_as::<_, WebhookRow>(&query)
+            .bind(owner_user_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn find_by_id(&self, id: i64) -> Result<WebhookRow, AppError> {
+        let query = format!("SELECT {} FROM webhooks WHERE id = ?", WEBHOOK_COLUMNS);
+        let row = sqlx::query_as::<_, WebhookRow>(&query)
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row)
+    }
+
+    pub async fn find_active(&self) -> Result<Vec<WebhookRow>, AppError> {
+        let query = format!("SELECT {} FROM webhooks WHERE active = 1", WEBHOOK_COLUMNS);
+        let rows = sqlx::query_as::<_, WebhookRow>(&query)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn create(
+        &self,
+        owner_user_id: &str,
+        scope: &str,
+        url: &str,
+        secret: &str,
+        events: &str,
+        active: bool,
+    ) -> Result<i64, AppError> {
+        let result = sqlx::query(
+            "INSERT INTO webhooks (owner_user_id, scope, url, secret, events, active)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(owner_user_id)
+        .bind(scope)
+        .bind(url)
+        .bind(secret)
+        .bind(events)
+        .bind(active)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    pub async fn update(
+        &self,
+        id: i64,
+        scope: &str,
+        url: &str,
+        events: &str,
+        active: bool,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE webhooks SET scope = ?, url = ?, events = ?, active = ? WHERE id = ?",
+        )
+        .bind(scope)
+        .bind(url)
+        .bind(events)
+        .bind(active)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete(&self, id: i64) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM webhooks WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ========================================================================
+    // Deliveries
+    // ========================================================================
+
+    pub async fn enqueue_delivery(
+        &self,
+        webhook_id: i64,
+        event_type: &str,
+        payload: &str,
+    ) -> Result<i64, AppError> {
+        let result = sqlx::query(
+            "INSERT INTO webhook_deliveries (webhook_id, event_type, payload, next_attempt_at)
+             VALUES (?, ?, ?, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))",
+        )
+        .bind(webhook_id)
+        .bind(event_type)
+        .bind(payload)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Pending deliveries whose next attempt is due, oldest first
+    pub async fn find_due_deliveries(
+        &self,
+        now: &str,
+        limit: i64,
+    ) -> Result<Vec<WebhookDeliveryRow>, AppError> {
+        let query = format!(
+            "SELECT {} FROM webhook_deliveries
+             WHERE status = 'pending' AND next_attempt_at <= ?
+             ORDER BY next_attempt_at, id
+             LIMIT ?",
+            DELIVERY_COLUMNS
+        );
+        let rows = sqlx::query_as::<_, WebhookDeliveryRow>(&query)
+            .bind(now)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn find_deliveries(
+        &self,
+        webhook_id: i64,
+        limit: i64,
+    ) -> Result<Vec<WebhookDeliveryRow>, AppError> {
+        let query = format!(
+            "SELECT {} FROM webhook_deliveries
+             WHERE webhook_id = ?
+             ORDER BY created_at DESC, id DESC
+             LIMIT ?",
+            DELIVERY_COLUMNS
+        );
+        let rows = sqlx::query_as::<_, WebhookDeliveryRow>(&query)
+            .bind(webhook_id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn mark_delivered(&self, id: i64, response_status: u16) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE webhook_deliveries
+             SET status = 'delivered', attempts = attempts + 1, response_status = ?,
+                 last_error = NULL, delivered_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+             WHERE id = ?",
+        )
+        .bind(response_status)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn mark_retry(
+        &self,
+        id: i64,
+        next_attempt_at: &str,
+        response_status: Option<u16>,
+        error: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE webhook_deliveries
+             SET attempts = attempts + 1, next_attempt_at = ?, response_status = ?, last_error = ?
+             WHERE id = ?",
+        )
+        .bind(next_attempt_at)
+        .bind(response_status)
+        .bind(error)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn mark_failed(
+        &self,
+        id: i64,
+        response_status: Option<u16>,
+        error: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE webhook_deliveries
+             SET status = 'failed', attempts = attempts + 1, response_status = ?, last_error = ?
+             WHERE id = ?",
+        )
+        .bind(response_status)
+        .bind(error)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 use crate::errors::AppError;
 use crate::models::user::{Claims, UserRole};
 use axum::{
-    extract::{Request, State},
+    extract::{Query, Request, State},
     http::StatusCode,
     middleware::Next,
     response::Response,
@@ -10,6 +10,7 @@ use axum::{
 use jsonwebtoken::{DecodingKey, Validation, decode};
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
+use std::collections::HashMap;
 
 // ============================================================================
 // JWT Claims Structure
@@ -38,12 +39,46 @@ pub async fn auth_middleware(
     // Extract token from Authorization header
     let token = extract_token(&req)?;
 
+    let claims = authenticate(&pool, &token).await?;
+
+    // Attach claims to request extensions for handlers to use
+    req.extensions_mut().insert(claims);
+
+    Ok(next.run(req).await)
+}
+
+// ============================================================================
+// WebSocket Auth Middleware - Browsers can't set headers on a WebSocket
+// handshake, so the token may also come as ?token=
+// ============================================================================
+pub async fn ws_auth_middleware(
+    State(pool): State<SqlitePool>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let token = match extract_token(&req) {
+        Ok(token) => token,
+        Err(header_error) => Query::<HashMap<String, String>>::try_from_uri(req.uri())
+            .ok()
+            .and_then(|Query(mut params)| params.remove("token"))
+            .ok_or(header_error)?,
+    };
+
+    let claims = authenticate(&pool, &token).await?;
+
+    req.extensions_mut().insert(claims);
+
+    Ok(next.run(req).await)
+}
+
+/// Verifies the JWT and checks lockout status
+async fn authenticate(pool: &SqlitePool, token: &str) -> Result<Claims, AppError> {
     // Verify JWT
     let jwt_secret = std::env::var("JWT_SECRET")
         .map_err(|_| AppError::InternalError("JWT_SECRET not configured".to_string()))?;
 
     let token_data = decode::<Claims>(
-        &token,
+        token,
         &DecodingKey::from_secret(jwt_secret.as_bytes()),
         &Validation::default(),
     )
@@ -62,7 +97,7 @@ pub async fn auth_middleware(
          AND (lockout_until IS NULL OR lockout_until > datetime('now'))",
     )
     .bind(&claims.sub)
-    .fetch_optional(&pool)
+    .fetch_optional(pool)
     .await
     .map_err(|e| AppError::DatabaseError(e.to_string()))?
     .unwrap_or(false);
@@ -71,10 +106,7 @@ pub async fn auth_middleware(
         return Err(AppError::Forbidden("Account is locked".to_string()));
     }
 
-    // Attach claims to request extensions for handlers to use
-    req.extensions_mut().insert(claims);
-
-    Ok(next.run(req).await)
+    Ok(claims)
 }
 
 // ============================================================================
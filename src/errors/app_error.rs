@@ -6,8 +6,25 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
 };
+use serde::Serialize;
 use serde_json::json;
 
+///One problem with one field of a request body
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum AppError {
     NotFound(String),
@@ -19,6 +36,31 @@ pub enum AppError {
     BadRequest(String),
     InternalError(String),
     Conflict(String),
+    ///400 with every failing field listed, so a form can mark them all at once
+    InvalidFields(Vec<FieldError>),
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppError::NotFound(msg)
+            | AppError::DatabaseError(msg)
+            | AppError::ValidationError(msg)
+            | AppError::SerializationError(msg)
+            | AppError::Unauthorized(msg)
+            | AppError::Forbidden(msg)
+            | AppError::BadRequest(msg)
+            | AppError::InternalError(msg)
+            | AppError::Conflict(msg) => write!(f, "{}", msg),
+            AppError::InvalidFields(fields) => {
+                let messages: Vec<String> = fields
+                    .iter()
+                    .map(|e| format!("{}: {}", e.field, e.message))
+                    .collect();
+                write!(f, "{}", messages.join("; "))
+            }
+        }
+    }
 }
 
 impl IntoResponse for AppError {
@@ -33,6 +75,13 @@ impl IntoResponse for AppError {
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
             AppError::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
             AppError::Conflict(msg) => (StatusCode::CONFLICT, msg),
+            AppError::InvalidFields(fields) => {
+                let body = Json(json!({
+                    "error": "Validation failed",
+                    "fields": fields,
+                }));
+                return (StatusCode::BAD_REQUEST, body).into_response();
+            }
         };
 
         let body = Json(json!({
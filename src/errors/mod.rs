@@ -2,4 +2,4 @@
 // src/errors/mod.rs
 // ============================================================================
 pub mod app_error;
-pub use app_error::AppError;
+pub use app_error::{AppError, FieldError};
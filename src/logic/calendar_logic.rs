@@ -0,0 +1,255 @@
+// ============================================================================
+// src/logic/calendar_logic.rs - iCalendar export and subscribable feeds
+// ============================================================================
+use crate::context::{CalendarTokenContext, EventContext, MicroeventContext, RsvpContext};
+use crate::errors::AppError;
+use crate::logic::UserCollectionLogic;
+use crate::models::dto::EventResponse;
+use crate::models::event_models::local_date;
+use crate::models::microevents_models::{ApprovalStatus, Microevent};
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+const PRODID: &str = "-//Nomad Events//Festival Events API//EN";
+const UID_DOMAIN: &str = "nomad-events";
+
+pub struct CalendarLogic {
+    tokens: CalendarTokenContext,
+    events_context: EventContext,
+    microevents_context: MicroeventContext,
+    rsvps: RsvpContext,
+    user_collection_logic: Arc<UserCollectionLogic>,
+}
+
+impl CalendarLogic {
+    pub fn new(
+        tokens: CalendarTokenContext,
+        events_context: EventContext,
+        microevents_context: MicroeventContext,
+        rsvps: RsvpContext,
+        user_collection_logic: Arc<UserCollectionLogic>,
+    ) -> Self {
+        Self {
+            tokens,
+            events_context,
+            microevents_context,
+            rsvps,
+            user_collection_logic,
+        }
+    }
+
+    ///One event and its microevent schedule
+    pub async fn event_ics(&self, id: i64) -> Result<String, AppError> {
+        let row = self.events_context.find_by_id(id).await?;
+        let event = EventResponse::from_row(row)?;
+        let microevents: Vec<Microevent> = self
+            .microevents_context
+            .find_by_event(id)
+            .await?
+            .into_iter()
+            .filter(|m| !m.archive && m.approval_status == ApprovalStatus::Approved)
+            .collect();
+
+        let mut calendar = Calendar::new(&event.name);
+        calendar.add_event(&event);
+        for microevent in &microevents {
+            calendar.add_microevent(microevent);
+        }
+
+        Ok(calendar.finish())
+    }
+
+    ///Creates a new feed token. The token itself is only returned here, we keep the hash.
+    pub async fn create_feed_token(&self, user_id: &str) -> Result<String, AppError> {
+        let bytes: [u8; 32] = rand::random();
+        let token = hex::encode(bytes);
+
+        self.tokens.create(user_id, &hash_token(&token)).await?;
+
+        Ok(token)
+    }
+
+    pub async fn revoke_feed_tokens(&self, user_id: &str) -> Result<u64, AppError> {
+        self.tokens.revoke_all(user_id).await
+    }
+
+    ///Saved events, and saved microevents plus the ones with a seat, for the
+    ///owner of the token
+    pub async fn user_feed(&self, token: &str) -> Result<String, AppError> {
+        let user_id = self
+            .tokens
+            .find_user_id(&hash_token(token))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Calendar feed not found".to_string()))?;
+
+        let events = self
+            .user_collection_logic
+            .get_saved_events(&user_id)
+            .await?;
+        let mut microevents = self
+            .user_collection_logic
+            .get_saved_microevents(&user_id)
+            .await?;
+        let going: Vec<i64> = self
+            .rsvps
+            .find_going_ids(&user_id)
+            .await?
+            .into_iter()
+            .filter(|id| !microevents.iter().any(|m| m.id == *id))
+            .collect();
+        if !going.is_empty() {
+            microevents.extend(self.microevents_context.get_by_id_list(going).await?);
+        }
+
+        let mut calendar = Calendar::new("My Nomad Events");
+        calendar.line("X-PUBLISHED-TTL:PT1H");
+        for event in &events {
+            calendar.add_event(event);
+        }
+        for microevent in microevents
+            .iter()
+            .filter(|m| !m.archive && m.approval_status == ApprovalStatus::Approved)
+        {
+            calendar.add_microevent(microevent);
+        }
+
+        Ok(calendar.finish())
+    }
+}
+
+fn hash_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+///RFC 5545 writer. Events are all-day (VALUE=DATE) on the days in the event's own zone,
+///microevents are written in UTC so every client converts them to local time.
+struct Calendar {
+    body: String,
+    stamp: String,
+}
+
+impl Calendar {
+    fn new(name: &str) -> Self {
+        let mut calendar = Self {
+            body: String::new(),
+            stamp: utc_stamp(&Utc::now()),
+        };
+        calendar.line("BEGIN:VCALENDAR");
+        calendar.line("VERSION:2.0");
+        calendar.line(&format!("PRODID:{}", PRODID));
+        calendar.line("CALSCALE:GREGORIAN");
+        calendar.line("METHOD:PUBLISH");
+        calendar.line(&format!("X-WR-CALNAME:{}", escape_text(name)));
+        calendar
+    }
+
+    fn add_event(&mut self, event: &EventResponse) {
+        let (Some(id), Some(start)) = (event.id, event.date_info.start_date) else {
+            return; // nothing to put on a calendar yet
+        };
+        // the days as they are on site, not in UTC
+        let tz = event.location_info.tz().unwrap_or(Tz::UTC);
+        let start = local_date(start, tz);
+        // DTEND is exclusive for all-day entries
+        let end = event
+            .date_info
+            .end_date
+            .map_or(start, |end| local_date(end, tz))
+            + Duration::days(1);
+
+        self.line("BEGIN:VEVENT");
+        self.line(&format!("UID:event-{}@{}", id, UID_DOMAIN));
+        self.line(&format!("DTSTAMP:{}", self.stamp));
+        self.line(&format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")));
+        self.line(&format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")));
+        self.line(&format!("SUMMARY:{}", escape_text(&event.name)));
+        if !event.description.is_empty() {
+            self.line(&format!("DESCRIPTION:{}", escape_text(&event.description)));
+        }
+
+        let location = &event.location_info;
+        let place = match &location.venue_name {
+            Some(venue) if !location.address.is_empty() => {
+                format!("{}, {}", venue, location.address)
+            }
+            Some(venue) => venue.clone(),
+            None => location.address.clone(),
+        };
+        if !place.is_empty() {
+            self.line(&format!("LOCATION:{}", escape_text(&place)));
+        }
+        if location.latitude != 0.0 || location.longitude != 0.0 {
+            self.line(&format!(
+                "GEO:{:.6};{:.6}",
+                location.latitude, location.longitude
+            ));
+        }
+        if let Some(website) = &event.website {
+            self.line(&format!("URL:{}", website));
+        }
+        self.line("END:VEVENT");
+    }
+
+    fn add_microevent(&mut self, microevent: &Microevent) {
+        let Some(start) = microevent.start_time else {
+            return;
+        };
+
+        self.line("BEGIN:VEVENT");
+        self.line(&format!("UID:microevent-{}@{}", microevent.id, UID_DOMAIN));
+        self.line(&format!("DTSTAMP:{}", self.stamp));
+        self.line(&format!("DTSTART:{}", utc_stamp(&start)));
+        if let Some(end) = microevent.end_time {
+            self.line(&format!("DTEND:{}", utc_stamp(&end)));
+        }
+        self.line(&format!("SUMMARY:{}", escape_text(&microevent.name)));
+        if microevent.cancelled {
+            self.line("STATUS:CANCELLED");
+        }
+        if let Some(description) = &microevent.description {
+            self.line(&format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        self.line(&format!(
+            "RELATED-TO:event-{}@{}",
+            microevent.event_id, UID_DOMAIN
+        ));
+        self.line("END:VEVENT");
+    }
+
+    ///Content lines end in CRLF and are folded at 75 octets
+    fn line(&mut self, content: &str) {
+        let mut width = 0;
+        for c in content.chars() {
+            let len = c.len_utf8();
+            if width + len > 75 {
+                self.body.push_str("\r\n ");
+                width = 1;
+            }
+            self.body.push(c);
+            width += len;
+        }
+        self.body.push_str("\r\n");
+    }
+
+    fn finish(mut self) -> String {
+        self.line("END:VCALENDAR");
+        self.body
+    }
+}
+
+fn utc_stamp(value: &DateTime<Utc>) -> String {
+    value.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
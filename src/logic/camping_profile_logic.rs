@@ -3,7 +3,9 @@
 // ============================================================================
 
 use crate::context::CampingProfileContext;
+use crate::models::dto::SchemaUpgradeReport;
 use crate::models::event_models::CampingProfile;
+use crate::models::stored_schema::{self, CURRENT_SCHEMA_VERSION, StoredKind};
 //use crate::repositories::CampingRepository;
 use crate::errors::AppError;
 
@@ -51,6 +53,41 @@ impl CampingProfileLogic {
         Ok(())
     }
 
+    /// Admin job - rewrites every profile stored below the current schema_version
+    pub async fn upgrade_stored_profiles(&self) -> Result<SchemaUpgradeReport, AppError> {
+        let rows = self.repository.find_all_camping_data().await?;
+        let mut report = SchemaUpgradeReport {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            scanned: rows.len(),
+            upgraded: 0,
+            failed: Vec::new(),
+        };
+
+        for (id, raw) in rows {
+            let current = serde_json::from_str::<serde_json::Value>(&raw)
+                .map(|value| stored_schema::stored_version(&value) == CURRENT_SCHEMA_VERSION)
+                .unwrap_or(false);
+            if current {
+                continue;
+            }
+
+            let profile: CampingProfile =
+                match stored_schema::from_stored_json(StoredKind::CampingProfile, &raw) {
+                    Ok(profile) => profile,
+                    Err(e) => {
+                        tracing::warn!("camping profile {} could not be upgraded: {}", id, e);
+                        report.failed.push(id);
+                        continue;
+                    }
+                };
+
+            self.repository.update(id, &profile).await?;
+            report.upgraded += 1;
+        }
+
+        Ok(report)
+    }
+
     pub async fn delete_profile(&self, id: i64) -> Result<(), AppError> {
         let deleted = self.repository.delete(id).await?;
 
@@ -0,0 +1,140 @@
+// ============================================================================
+// src/logic/change_feed.rs - In-process fan-out of schedule changes
+// ============================================================================
+use crate::models::live_models::ScheduleChange;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+
+// Subscribers that fall further behind than this get a resync message
+const CHANNEL_CAPACITY: usize = 1024;
+// How many recent changes a reconnecting SSE client can catch up on
+const LOG_CAPACITY: usize = 1000;
+
+///EventLogic and MicroeventLogic publish here after each successful write.
+///Every open WebSocket or SSE stream holds a receiver and filters on the
+///events it follows. The last LOG_CAPACITY changes are kept so a client can
+///resume from its Last-Event-ID.
+pub struct ChangeFeed {
+    sender: broadcast::Sender<ScheduleChange>,
+    log: Mutex<ChangeLog>,
+    // Sequence numbers restart with the process, ids from before a restart are stale
+    epoch: i64,
+}
+
+struct ChangeLog {
+    last_seq: u64,
+    entries: VecDeque<ScheduleChange>,
+}
+
+///What a resuming client gets before the live changes
+pub enum Replay {
+    ///No Last-Event-ID, start from now
+    Fresh,
+    ///Everything published after the given id
+    Changes(Vec<ScheduleChange>),
+    ///The id is older than the log or from before a restart, reload from the API
+    Gap,
+}
+
+impl ChangeFeed {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            sender,
+            log: Mutex::new(ChangeLog {
+                last_seq: 0,
+                entries: VecDeque::with_capacity(LOG_CAPACITY),
+            }),
+            epoch: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    pub fn publish(&self, mut change: ScheduleChange) {
+        // sequence and send under one lock, so receivers see changes in seq order
+        let mut log = self.lock();
+        log.last_seq += 1;
+        change.seq = log.last_seq;
+
+        if log.entries.len() == LOG_CAPACITY {
+            log.entries.pop_front();
+        }
+        log.entries.push_back(change.clone());
+
+        // an error only means nobody is listening right now
+        let _ = self.sender.send(change);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ScheduleChange> {
+        self.sender.subscribe()
+    }
+
+    ///Subscribes and collects the replay in one step, so no change falls
+    ///between the two. Live changes with seq <= the replay's last are repeats.
+    pub fn resume(
+        &self,
+        last_event_id: Option<&str>,
+    ) -> (broadcast::Receiver<ScheduleChange>, Replay) {
+        let log = self.lock();
+        let receiver = self.sender.subscribe();
+
+        let replay = match last_event_id {
+            None => Replay::Fresh,
+            Some(id) => match self.parse_event_id(id) {
+                Some(seq) => log.since(seq).map_or(Replay::Gap, Replay::Changes),
+                None => Replay::Gap,
+            },
+        };
+
+        (receiver, replay)
+    }
+
+    ///Changes after seq, None when some of them have already left the log
+    pub fn since(&self, seq: u64) -> Option<Vec<ScheduleChange>> {
+        self.lock().since(seq)
+    }
+
+    ///The SSE id for a change, "{epoch}-{seq}"
+    pub fn event_id(&self, seq: u64) -> String {
+        format!("{}-{}", self.epoch, seq)
+    }
+
+    fn parse_event_id(&self, id: &str) -> Option<u64> {
+        let (epoch, seq) = id.trim().split_once('-')?;
+        if epoch.parse::<i64>().ok()? != self.epoch {
+            return None;
+        }
+        seq.parse().ok()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ChangeLog> {
+        // the log is always left consistent, a panic elsewhere doesn't poison it
+        self.log.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ChangeLog {
+    fn since(&self, seq: u64) -> Option<Vec<ScheduleChange>> {
+        if seq > self.last_seq {
+            return None;
+        }
+        let oldest = self.entries.front().map_or(self.last_seq + 1, |c| c.seq);
+        if seq + 1 < oldest {
+            return None;
+        }
+
+        Some(
+            self.entries
+                .iter()
+                .filter(|c| c.seq > seq)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+impl Default for ChangeFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,406 @@
+// ============================================================================
+// src/logic/event_import_logic.rs - Bulk event import for admins
+// ============================================================================
+use crate::context::EventContext;
+use crate::errors::AppError;
+use crate::logic::{EventLogic, UserCollectionLogic};
+use crate::models::dto::{ImportReport, ImportRequest, ImportRowError};
+use crate::models::event_models::{NomEvent, parse_event_date};
+use crate::models::live_models::ScheduleChangeKind;
+use crate::models::user::Claims;
+use chrono::NaiveDate;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+// CSV cells for these fields are parsed as numbers
+const NUMERIC_FIELDS: &[&str] = &[
+    "event_type_id",
+    "series_id",
+    "latitude",
+    "longitude",
+    "max_stay_nights",
+    "max_length_feet",
+    "max_width_feet",
+    "max_decibel_limit",
+    "distance_from_neighbors_feet",
+];
+
+// and these as true/false (also yes/no), everything else stays text
+const BOOLEAN_FIELDS: &[&str] = &[
+    "archive",
+    "require_microevent_approval",
+    "single_day",
+    "early_arrival_available",
+    "late_departure_available",
+    "camping_allowed",
+    "walking_distance",
+    "tent_camping",
+    "campsite_reservations_required",
+    "primitive_camping",
+    "developed_campsites",
+    "pet_friendly",
+    "fires_allowed",
+    "electric",
+    "water",
+    "sewer",
+    "allowed",
+    "class_a_allowed",
+    "class_b_allowed",
+    "class_c_allowed",
+    "travel_trailers_allowed",
+    "fifth_wheel_allowed",
+    "hookups_available",
+    "dump_station",
+    "van_camping",
+    "car_camping",
+    "truck_camping",
+    "rooftop_tent_allowed",
+    "bathrooms",
+    "showers",
+    "potable_water",
+    "wifi",
+    "firewood_available",
+    "ice_available",
+    "trash_service",
+    "recycling",
+    "laundry",
+    "generators_allowed",
+    "inverter_generators_only",
+    "propane_generators_allowed",
+    "gasoline_generators_allowed",
+    "diesel_generators_allowed",
+    "designated_generator_areas",
+    "all_day_restriction",
+];
+
+pub struct EventImportLogic {
+    repository: EventContext,
+    event_logic: Arc<EventLogic>,
+    user_collection_logic: Arc<UserCollectionLogic>,
+}
+
+impl EventImportLogic {
+    pub fn new(
+        repository: EventContext,
+        event_logic: Arc<EventLogic>,
+        user_collection_logic: Arc<UserCollectionLogic>,
+    ) -> Self {
+        Self {
+            repository,
+            event_logic,
+            user_collection_logic,
+        }
+    }
+
+    ///Validates every row, then creates them all in one transaction.
+    ///Any row error (or a dry run) means nothing is written.
+    pub async fn import(
+        &self,
+        request: ImportRequest,
+        claims: Claims,
+    ) -> Result<ImportReport, AppError> {
+        let rows: Vec<Value> = match (request.events, request.csv) {
+            (Some(events), None) => events,
+            (None, Some(csv)) => csv_to_rows(&csv, &request.columns)?,
+            _ => {
+                return Err(AppError::ValidationError(
+                    "Send either events (JSON) or csv, not both".to_string(),
+                ));
+            }
+        };
+
+        let event_types: HashSet<i64> = self
+            .repository
+            .find_event_type_ids()
+            .await?
+            .into_iter()
+            .collect();
+
+        // lowercase name + start day -> existing event id
+        let mut existing: HashMap<(String, Option<NaiveDate>), i64> = self
+            .repository
+            .find_duplicate_keys()
+            .await?
+            .into_iter()
+            .map(|(id, name, start_date)| {
+                let day = start_date
+                    .as_deref()
+                    .and_then(parse_event_date)
+                    .map(|d| d.date_naive());
+                ((name.trim().to_lowercase(), day), id)
+            })
+            .collect();
+
+        let mut report = ImportReport {
+            dry_run: request.dry_run,
+            total_rows: rows.len(),
+            valid_rows: 0,
+            created_ids: Vec::new(),
+            duplicates: Vec::new(),
+            errors: Vec::new(),
+        };
+        let mut accepted: Vec<NomEvent> = Vec::new();
+
+        for (index, value) in rows.into_iter().enumerate() {
+            let row = index + 1;
+            let name = value
+                .get("name")
+                .and_then(Value::as_str)
+                .map(|n| n.to_string());
+            let row_error = |error: String| ImportRowError {
+                row,
+                name: name.clone(),
+                error,
+            };
+
+            // The lenient NomEvent deserializers still apply here
+            let mut event: NomEvent = match serde_json::from_value(value) {
+                Ok(event) => event,
+                Err(e) => {
+                    report.errors.push(row_error(e.to_string()));
+                    continue;
+                }
+            };
+            event.id = None;
+            // series membership is set through PUT /event/{id}/series, by its owner
+            event.series_id = None;
+            event.user_id = Some(claims.sub.clone());
+
+            if let Err(e) = self.event_logic.validate_event(&event) {
+                report.errors.push(row_error(e.to_string()));
+                continue;
+            }
+            if !event_types.contains(&event.event_type_id) {
+                report.errors.push(row_error(format!(
+                    "Unknown event_type_id {}",
+                    event.event_type_id
+                )));
+                continue;
+            }
+
+            let key = (
+                event.name.trim().to_lowercase(),
+                event.date_info.start_date.map(|d| d.date_naive()),
+            );
+            if let Some(id) = existing.get(&key) {
+                let duplicate = row_error(if *id > 0 {
+                    format!("Duplicate of existing event {}", id)
+                } else {
+                    format!("Duplicate of import row {}", -id)
+                });
+                if request.skip_duplicates {
+                    report.duplicates.push(duplicate);
+                } else {
+                    report.errors.push(duplicate);
+                }
+                continue;
+            }
+            // negative ids mark rows from this import
+            existing.insert(key, -(row as i64));
+
+            accepted.push(event);
+        }
+
+        report.valid_rows = accepted.len();
+
+        if request.dry_run || !report.errors.is_empty() || accepted.is_empty() {
+            return Ok(report);
+        }
+
+        // make sure the collection row exists, create_many appends to it
+        self.user_collection_logic.get(&claims.sub).await?;
+        report.created_ids = self.repository.create_many(&accepted, &claims.sub).await?;
+
+        for id in &report.created_ids {
+            self.event_logic
+                .publish_change(ScheduleChangeKind::Created, *id)
+                .await;
+        }
+
+        Ok(report)
+    }
+}
+
+///Turns CSV text into one JSON object per row, nested by the dotted field paths
+fn csv_to_rows(csv: &str, columns: &HashMap<String, String>) -> Result<Vec<Value>, AppError> {
+    let mut records = parse_csv(csv)?.into_iter();
+    let header = records
+        .next()
+        .ok_or_else(|| AppError::ValidationError("CSV is empty".to_string()))?;
+
+    let paths: Vec<String> = header
+        .iter()
+        .map(|h| {
+            let h = h.trim();
+            columns.get(h).cloned().unwrap_or_else(|| h.to_string())
+        })
+        .collect();
+
+    let mut rows = Vec::new();
+    for record in records {
+        // skip blank lines
+        if record.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+
+        let mut row = Value::Object(Map::new());
+        for (path, cell) in paths.iter().zip(record.iter()) {
+            let cell = cell.trim();
+            if path.is_empty() || cell.is_empty() {
+                continue;
+            }
+            set_path(&mut row, path, csv_cell_value(path, cell));
+        }
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+fn csv_cell_value(path: &str, cell: &str) -> Value {
+    let field = path.rsplit('.').next().unwrap_or(path);
+    if NUMERIC_FIELDS.contains(&field) {
+        if let Ok(int) = cell.parse::<i64>() {
+            return Value::from(int);
+        }
+        if let Ok(float) = cell.parse::<f64>() {
+            return Value::from(float);
+        }
+        // left as text so the row fails with a serde error naming the field
+        return Value::from(cell);
+    }
+
+    if BOOLEAN_FIELDS.contains(&field) {
+        // anything else is left as text and fails the row with a serde error
+        match cell.to_lowercase().as_str() {
+            "true" | "yes" => return Value::Bool(true),
+            "false" | "no" => return Value::Bool(false),
+            _ => {}
+        }
+    }
+
+    Value::from(cell)
+}
+
+fn set_path(root: &mut Value, path: &str, value: Value) {
+    let mut current = root;
+    let mut segments = path.split('.').peekable();
+    while let Some(segment) = segments.next() {
+        let Some(object) = current.as_object_mut() else {
+            return; // a parent was set to a plain value by another column
+        };
+        if segments.peek().is_none() {
+            object.insert(segment.to_string(), value);
+            return;
+        }
+        current = object
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+}
+
+///RFC 4180: comma separated, fields may be quoted, "" is an escaped quote
+fn parse_csv(input: &str) -> Result<Vec<Vec<String>>, AppError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = input.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(AppError::ValidationError(
+            "CSV has an unterminated quoted field".to_string(),
+        ));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn quoted_fields_keep_commas_and_escaped_quotes() {
+        let records = parse_csv("name,website\n\"Rally, West\",\"say \"\"hi\"\"\"\n").unwrap();
+        assert_eq!(
+            records,
+            vec![
+                vec!["name".to_string(), "website".to_string()],
+                vec!["Rally, West".to_string(), "say \"hi\"".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn crlf_and_bom_are_ignored() {
+        let records = parse_csv("\u{feff}name,city\r\nRally,Quartzsite\r\n").unwrap();
+        assert_eq!(records[0], vec!["name", "city"]);
+        assert_eq!(records[1], vec!["Rally", "Quartzsite"]);
+        assert_eq!(records.len(), 2);
+    }
+
+    #[test]
+    fn unterminated_quote_is_an_error() {
+        let error = parse_csv("name\n\"Rally\n").unwrap_err();
+        assert!(matches!(error, AppError::ValidationError(_)));
+    }
+
+    #[test]
+    fn rows_convert_booleans_numbers_and_nested_paths() {
+        let columns = HashMap::from([("Showers".to_string(), "amenities.showers".to_string())]);
+        let rows = csv_to_rows(
+            "name,Showers,camping_info.pet_friendly,location_info.latitude\n\
+             Rally,yes,No,33.66\n\
+             ,,,\n",
+            &columns,
+        )
+        .unwrap();
+
+        assert_eq!(
+            rows,
+            vec![json!({
+                "name": "Rally",
+                "amenities": { "showers": true },
+                "camping_info": { "pet_friendly": false },
+                "location_info": { "latitude": 33.66 },
+            })]
+        );
+    }
+
+    #[test]
+    fn unknown_boolean_stays_text() {
+        assert_eq!(csv_cell_value("amenities.showers", "maybe"), json!("maybe"));
+    }
+}
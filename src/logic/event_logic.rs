@@ -3,24 +3,53 @@
 // ============================================================================
 use crate::context::EventContext;
 use crate::errors::AppError;
-use crate::logic::UserCollectionLogic;
-use crate::models::dto::EventResponse;
+use crate::logic::{ChangeFeed, NotificationService, UserCollectionLogic, WebhookLogic};
+use crate::models::dto::{
+    BrokenEventRow, EventQueryParams, EventResponse, IntegrityReport, SchemaUpgradeReport,
+};
 use crate::models::event_models::NomEvent;
+use crate::models::geojson::{
+    EventFeatureProperties, Feature, FeatureCollection, MapCluster, MapClusterResponse,
+};
+use crate::models::live_models::{ScheduleChange, ScheduleChangeKind};
+use crate::models::stored_schema::{self, CURRENT_SCHEMA_VERSION, StoredKind};
 //use crate::repositories::EventRepository;
 use crate::logic::user_collection_logic;
 use crate::models::user::Claims;
+use crate::models::webhook_models::{EVENT_CREATED, EVENT_DELETED, EVENT_UPDATED};
+use chrono_tz::Tz;
 use serde_json::json;
+use std::collections::HashMap;
 use std::sync::Arc;
+
+// Above this zoom the map gets individual events instead of clusters
+const CLUSTER_MAX_ZOOM: u8 = 11;
+const MAX_ZOOM: u8 = 22;
+// Grid cells per 256px map tile, roughly one cluster per 64px
+const CELLS_PER_TILE: f64 = 4.0;
+
 pub struct EventLogic {
     repository: EventContext,
     user_collection_logic: Arc<UserCollectionLogic>, //userCollectionLogic: UserCollectionLogic,
+    notification_service: Arc<NotificationService>,
+    webhook_logic: Arc<WebhookLogic>,
+    change_feed: Arc<ChangeFeed>,
 }
 
 impl EventLogic {
-    pub fn new(repository: EventContext, user_collection_logic: Arc<UserCollectionLogic>) -> Self {
+    pub fn new(
+        repository: EventContext,
+        user_collection_logic: Arc<UserCollectionLogic>,
+        notification_service: Arc<NotificationService>,
+        webhook_logic: Arc<WebhookLogic>,
+        change_feed: Arc<ChangeFeed>,
+    ) -> Self {
         Self {
             repository,
             user_collection_logic,
+            notification_service,
+            webhook_logic,
+            change_feed,
         }
     }
 
@@ -35,10 +64,7 @@ impl EventLogic {
     pub async fn get_all_events(&self) -> Result<Vec<EventResponse>, AppError> {
         let rows = self.repository.find_all().await?;
 
-        let events: Vec<EventResponse> = rows
-            .into_iter()
-            .filter_map(|row| EventResponse::from_row(row).ok())
-            .collect();
+        let events = EventResponse::from_rows(rows);
 
         //println!("{:#?}", (events.clone()));
 
@@ -62,10 +88,7 @@ impl EventLogic {
         ////get user saved
         //let user_saved_id_list = self.userCollectionLogic.get_user_saved_events().await?;
 
-        let events: Vec<EventResponse> = rows
-            .into_iter()
-            .filter_map(|row| EventResponse::from_row(row).ok())
-            .collect();
+        let events = EventResponse::from_rows(rows);
 
         Ok(events)
     }
@@ -86,10 +109,7 @@ impl EventLogic {
         //println!("number of rows found: {}", rows.iter().count());
         //println!("{:#?}", (rows.clone()));
 
-        let events: Vec<EventResponse> = rows
-            .into_iter()
-            .filter_map(|row| EventResponse::from_row(row).ok())
-            .collect();
+        let events = EventResponse::from_rows(rows);
 
         //println!("events: {}", events.iter().map(|res| res.to_string())
         //.collect::<Vec<String>>()
@@ -98,20 +118,157 @@ impl EventLogic {
         Ok(events)
     }
 
-    pub async fn get_by_id_list(&self, input: Vec<i64>) -> Result<Vec<EventResponse>, AppError> {
-        let rows = self.repository.get_by_id_list(input).await?;
+    pub async fn search_events(
+        &self,
+        params: &EventQueryParams,
+    ) -> Result<Vec<EventResponse>, AppError> {
+        if let Some(radius_miles) = params.radius_miles {
+            if radius_miles <= 0.0 || radius_miles > 500.0 {
+                return Err(AppError::ValidationError(
+                    "Radius must be between 0 and 500 miles".to_string(),
+                ));
+            }
+        }
+
+        let rows = self.repository.search(params).await?;
 
-        let events: Vec<EventResponse> = rows
+        let events = EventResponse::from_rows(rows);
+
+        Ok(events)
+    }
+
+    ///Same filters as search, shaped for the map. Events without coordinates are left out.
+    pub async fn get_events_geojson(
+        &self,
+        params: &EventQueryParams,
+    ) -> Result<FeatureCollection<EventFeatureProperties>, AppError> {
+        let events = self.search_events(params).await?;
+
+        let features = events
             .into_iter()
-            .filter_map(|row| EventResponse::from_row(row).ok())
+            .filter_map(|event| {
+                let id = event.id?;
+                let location = &event.location_info;
+                if location.latitude == 0.0 && location.longitude == 0.0 {
+                    return None;
+                }
+
+                Some(Feature::point(
+                    id,
+                    location.longitude,
+                    location.latitude,
+                    EventFeatureProperties {
+                        name: event.name,
+                        event_type_id: event.event_type.id,
+                        event_type: event.event_type.name,
+                        category: event.event_type.category,
+                        map_indicator: event.event_type.map_indicator,
+                        start_date: event.date_info.start_date,
+                        end_date: event.date_info.end_date,
+                        camping_allowed: event
+                            .camping_info
+                            .as_ref()
+                            .map(|c| c.camping_allowed)
+                            .unwrap_or(false),
+                    },
+                ))
+            })
+            .collect();
+
+        Ok(FeatureCollection::new(features))
+    }
+
+    ///Groups the events in the bbox into grid cells sized for the zoom level
+    pub async fn get_event_clusters(
+        &self,
+        params: &EventQueryParams,
+        zoom: u8,
+    ) -> Result<MapClusterResponse, AppError> {
+        if zoom > MAX_ZOOM {
+            return Err(AppError::ValidationError(format!(
+                "zoom must be between 0 and {}",
+                MAX_ZOOM
+            )));
+        }
+
+        let collection = self.get_events_geojson(params).await?;
+
+        if zoom > CLUSTER_MAX_ZOOM {
+            return Ok(MapClusterResponse {
+                zoom,
+                clustered: false,
+                cell_size_degrees: None,
+                clusters: Vec::new(),
+                events: Some(collection),
+            });
+        }
+
+        // Equirectangular grid, a tile at zoom z spans 360 / 2^z degrees
+        let cell_size = 360.0 / (2f64.powi(zoom as i32) * CELLS_PER_TILE);
+
+        // cell -> (latitude sum, longitude sum, cluster so far)
+        let mut cells: HashMap<(i64, i64), (f64, f64, MapCluster)> = HashMap::new();
+        for feature in collection.features {
+            let [longitude, latitude] = feature.geometry.coordinates;
+            let key = (
+                ((longitude + 180.0) / cell_size).floor() as i64,
+                ((latitude + 90.0) / cell_size).floor() as i64,
+            );
+
+            let (lat_sum, lon_sum, cluster) = cells.entry(key).or_insert_with(|| {
+                (
+                    0.0,
+                    0.0,
+                    MapCluster {
+                        latitude: 0.0,
+                        longitude: 0.0,
+                        count: 0,
+                        categories: Default::default(),
+                    },
+                )
+            });
+            *lat_sum += latitude;
+            *lon_sum += longitude;
+            cluster.count += 1;
+            *cluster
+                .categories
+                .entry(feature.properties.category)
+                .or_insert(0) += 1;
+        }
+
+        let mut clusters: Vec<MapCluster> = cells
+            .into_values()
+            .map(|(lat_sum, lon_sum, mut cluster)| {
+                cluster.latitude = lat_sum / cluster.count as f64;
+                cluster.longitude = lon_sum / cluster.count as f64;
+                cluster
+            })
             .collect();
+        clusters.sort_by_key(|c| std::cmp::Reverse(c.count));
+
+        Ok(MapClusterResponse {
+            zoom,
+            clustered: true,
+            cell_size_degrees: Some(cell_size),
+            clusters,
+            events: None,
+        })
+    }
+
+    pub async fn get_by_id_list(&self, input: Vec<i64>) -> Result<Vec<EventResponse>, AppError> {
+        let rows = self.repository.get_by_id_list(input).await?;
+
+        let events = EventResponse::from_rows(rows);
         Ok(events)
     }
 
-    pub async fn create_event(&self, event: NomEvent) -> Result<i64, AppError> {
+    pub async fn create_event(&self, mut event: NomEvent) -> Result<i64, AppError> {
         // Business logic: validate event data
         self.validate_event(&event)?;
 
+        // joining a series is checked by EventSeriesLogic::set_event_series
+        event.series_id = None;
+
         //get the user id out of the model
         let user_id = &event
             .user_id
@@ -125,6 +282,8 @@ impl EventLogic {
             .event_ownership(id, user_id)
             .await?;
 
+        self.publish_change(ScheduleChangeKind::Created, id).await;
+
         Ok(id)
     }
 
@@ -158,6 +317,22 @@ impl EventLogic {
             return Err(AppError::NotFound("Event not found".to_string()));
         }
 
+        // The update went through, a failed notification should not undo that
+        if let Err(e) = self
+            .notification_service
+            .notify_event_favorites(
+                id,
+                &claims.sub,
+                &format!("{} was updated", event.name),
+                "An event in your favorites has new details.",
+            )
+            .await
+        {
+            tracing::warn!("event {} update notifications failed: {}", id, e);
+        }
+
+        self.publish_change(ScheduleChangeKind::Updated, id).await;
+
         Ok(())
     }
 
@@ -178,10 +353,28 @@ impl EventLogic {
             }
         }
 
+        // Tell the people who favorited it before the event is gone
+        if let Ok(event) = self.repository.find_stored(id).await
+            && let Err(e) = self
+                .notification_service
+                .notify_event_removed(
+                    id,
+                    &claims.sub,
+                    &format!("{} was removed", event.name),
+                    "An event in your favorites is no longer listed.",
+                )
+                .await
+        {
+            tracing::warn!("event {} removal notifications failed: {}", id, e);
+        }
+
         let deleted = self.repository.delete(id).await?;
         if !deleted {
             return Err(AppError::NotFound("Event not found".to_string()));
         } else {
+            // before the ownership goes, "owned" webhooks are matched on it
+            self.publish_change(ScheduleChangeKind::Deleted, id).await;
+
             //send this data to the usercollection
             self.user_collection_logic
                 .remove_event_ownership(id, &claims.sub)
@@ -191,6 +384,115 @@ impl EventLogic {
         Ok(())
     }
 
+    ///Queues webhook deliveries and tells live subscribers about a change that
+    ///has already been written. Failures are logged, the write itself stands.
+    pub async fn publish_change(&self, change: ScheduleChangeKind, id: i64) {
+        let event = if change == ScheduleChangeKind::Deleted {
+            None
+        } else {
+            match self.get_event_by_id(id).await {
+                Ok(event) => serde_json::to_value(event).ok(),
+                Err(_) => None,
+            }
+        };
+
+        let event_type = match change {
+            ScheduleChangeKind::Created => EVENT_CREATED,
+            ScheduleChangeKind::Updated | ScheduleChangeKind::Archived => EVENT_UPDATED,
+            ScheduleChangeKind::Deleted => EVENT_DELETED,
+        };
+        let data = event.clone().unwrap_or_else(|| json!({ "id": id }));
+        if let Err(e) = self.webhook_logic.emit(event_type, id, None, data).await {
+            tracing::warn!("event {} {} webhooks failed: {}", id, event_type, e);
+        }
+
+        self.change_feed
+            .publish(ScheduleChange::for_event(id, change, event));
+    }
+
+    /// Finds stored events with problems, and why. "columns" and "event_type" rows
+    /// are left out of the list endpoints. "event_data" rows are still served from
+    /// the columns, only their legacy blob can not be read (e.g. by the upgrade job).
+    pub async fn scan_integrity(&self) -> Result<IntegrityReport, AppError> {
+        let stored = self.repository.find_all_event_data().await?;
+        let rows = self.repository.find_all().await?;
+        let mut broken = Vec::new();
+
+        // find_all joins event_types, anything missing from it has a dangling type
+        let joined: std::collections::HashSet<i64> = rows.iter().map(|row| row.id).collect();
+
+        for (id, raw) in &stored {
+            if !joined.contains(id) {
+                broken.push(BrokenEventRow {
+                    id: *id,
+                    source: "event_type".to_string(),
+                    error: "event_type_id does not match an event type".to_string(),
+                });
+            }
+
+            if let Err(e) = stored_schema::from_stored_json::<NomEvent>(StoredKind::Event, raw) {
+                broken.push(BrokenEventRow {
+                    id: *id,
+                    source: "event_data".to_string(),
+                    error: format!("legacy blob unreadable: {}", e),
+                });
+            }
+        }
+
+        for row in rows {
+            let id = row.id;
+            if let Err(e) = EventResponse::from_row(row) {
+                broken.push(BrokenEventRow {
+                    id,
+                    source: "columns".to_string(),
+                    error: e.to_string(),
+                });
+            }
+        }
+
+        broken.sort_by_key(|b| b.id);
+
+        Ok(IntegrityReport {
+            scanned: stored.len(),
+            broken,
+        })
+    }
+
+    /// Admin job - rewrites every event stored below the current schema_version.
+    /// Going through update also resyncs the typed detail tables.
+    pub async fn upgrade_stored_events(&self) -> Result<SchemaUpgradeReport, AppError> {
+        let rows = self.repository.find_all_event_data().await?;
+        let mut report = SchemaUpgradeReport {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            scanned: rows.len(),
+            upgraded: 0,
+            failed: Vec::new(),
+        };
+
+        for (id, raw) in rows {
+            let current = serde_json::from_str::<serde_json::Value>(&raw)
+                .map(|value| stored_schema::stored_version(&value) == CURRENT_SCHEMA_VERSION)
+                .unwrap_or(false);
+            if current {
+                continue;
+            }
+
+            let event: NomEvent = match stored_schema::from_stored_json(StoredKind::Event, &raw) {
+                Ok(event) => event,
+                Err(e) => {
+                    tracing::warn!("event {} could not be upgraded: {}", id, e);
+                    report.failed.push(id);
+                    continue;
+                }
+            };
+
+            self.repository.update_stored_data(id, &event).await?;
+            report.upgraded += 1;
+        }
+
+        Ok(report)
+    }
+
     ////adding the favorite and saved sections
     //pub async fn save_toggle(&self, id: i64, user_id: String) -> Result<(), AppError> {
     //let updated = self.repository.update(id, &event).await?;
@@ -212,8 +514,8 @@ impl EventLogic {
     //Ok(())
     //}
 
-    // Private business logic methods
-    fn validate_event(&self, event: &NomEvent) -> Result<(), AppError> {
+    // Business rules every create goes through, also used by the bulk import
+    pub fn validate_event(&self, event: &NomEvent) -> Result<(), AppError> {
         if event.name.trim().is_empty() {
             return Err(AppError::ValidationError(
                 "Event name cannot be empty".to_string(),
@@ -235,6 +537,15 @@ impl EventLogic {
             return Err(AppError::ValidationError("Invalid longitude".to_string()));
         }
 
+        if let Some(time_zone) = event.location_info.time_zone.as_deref()
+            && time_zone.parse::<Tz>().is_err()
+        {
+            return Err(AppError::ValidationError(format!(
+                "Unknown time zone {}, use an IANA name like America/Denver",
+                time_zone
+            )));
+        }
+
         // Validate dates
         if let (Some(start), Some(end)) = (event.date_info.start_date, event.date_info.end_date) {
             if end < start {
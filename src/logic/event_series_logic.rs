@@ -0,0 +1,322 @@
+// ============================================================================
+// src/logic/event_series_logic.rs - Business Logic Layer
+// ============================================================================
+use crate::context::{EventContext, EventSeriesContext};
+use crate::errors::AppError;
+use crate::logic::{EventLogic, UserCollectionLogic};
+use crate::models::dto::{EventResponse, EventSeriesResponse};
+use crate::models::event_models::{EventSeries, NomEvent, parse_event_date};
+use crate::models::live_models::ScheduleChangeKind;
+use crate::models::user::Claims;
+use chrono::{Datelike, Months};
+use std::sync::Arc;
+
+pub struct EventSeriesLogic {
+    repository: EventSeriesContext,
+    events_context: EventContext,
+    user_collection_logic: Arc<UserCollectionLogic>,
+    event_logic: Arc<EventLogic>,
+}
+
+impl EventSeriesLogic {
+    pub fn new(
+        repository: EventSeriesContext,
+        events_context: EventContext,
+        user_collection_logic: Arc<UserCollectionLogic>,
+        event_logic: Arc<EventLogic>,
+    ) -> Self {
+        Self {
+            repository,
+            events_context,
+            user_collection_logic,
+            event_logic,
+        }
+    }
+
+    pub async fn get_all_series(&self) -> Result<Vec<EventSeries>, AppError> {
+        self.repository.find_all().await
+    }
+
+    pub async fn get_series(&self, id: i64) -> Result<EventSeriesResponse, AppError> {
+        let series = self.repository.find_by_id(id).await?;
+        let rows = self.events_context.find_by_series(id).await?;
+
+        Ok(EventSeriesResponse {
+            series,
+            editions: EventResponse::from_rows(rows),
+        })
+    }
+
+    pub async fn create_series(&self, series: EventSeries) -> Result<i64, AppError> {
+        Self::validate_series(&series)?;
+        self.repository.create(&series).await
+    }
+
+    pub async fn update_series(&self, id: i64, series: EventSeries) -> Result<(), AppError> {
+        Self::validate_series(&series)?;
+
+        let updated = self.repository.update(id, &series).await?;
+        if !updated {
+            return Err(AppError::NotFound("Event series not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn delete_series(&self, id: i64) -> Result<(), AppError> {
+        // editions are kept, their series_id is cleared by the foreign key
+        let deleted = self.repository.delete(id).await?;
+        if !deleted {
+            return Err(AppError::NotFound("Event series not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    ///Series the user has favorited, each with all of its editions
+    pub async fn get_favorite_series(
+        &self,
+        user_id: &String,
+    ) -> Result<Vec<EventSeriesResponse>, AppError> {
+        let collection = self.user_collection_logic.get(user_id).await?;
+        let series_list = self
+            .repository
+            .get_by_id_list(collection.favorite_series)
+            .await?;
+
+        let mut output = Vec::new();
+        for series in series_list {
+            let Some(id) = series.id else {
+                continue;
+            };
+            let rows = self.events_context.find_by_series(id).await?;
+            output.push(EventSeriesResponse {
+                series,
+                editions: EventResponse::from_rows(rows),
+            });
+        }
+
+        Ok(output)
+    }
+
+    ///Copies an event one year forward as the next edition of its series.
+    ///Events that are not in a series yet get one named after them.
+    pub async fn clone_to_next_edition(&self, id: i64, claims: Claims) -> Result<i64, AppError> {
+        // Check if user is admin or superadmin (bypass ownership check)
+        let is_admin = claims.role == "admin" || claims.role == "super_admin";
+        if !is_admin {
+            let collection = self.user_collection_logic.get(&claims.sub).await?;
+            if !collection.created_events.contains(&id) {
+                return Err(AppError::Unauthorized(
+                    "You do not have permission to copy this event".to_string(),
+                ));
+            }
+        }
+
+        let event = self.events_context.find_stored(id).await?;
+
+        let start_date = event.date_info.start_date.ok_or_else(|| {
+            AppError::ValidationError(
+                "Event needs a start date before it can be copied to the next edition".to_string(),
+            )
+        })?;
+
+        let series_id = match event.series_id {
+            Some(series_id) => series_id,
+            None => {
+                let series_id = self
+                    .repository
+                    .create(&EventSeries {
+                        id: None,
+                        name: strip_year(&event.name, start_date.year()),
+                        description: Some(event.description.clone()),
+                        website: event.website.clone(),
+                    })
+                    .await?;
+                self.events_context.set_series(id, Some(series_id)).await?;
+                self.event_logic
+                    .publish_change(ScheduleChangeKind::Updated, id)
+                    .await;
+                series_id
+            }
+        };
+
+        let next = Self::next_edition(event, series_id, &claims.sub)?;
+
+        // Only one edition per start date
+        let next_start = next.date_info.start_date.map(|d| d.date_naive());
+        let existing = self.events_context.find_by_series(series_id).await?;
+        if let Some(row) = existing.iter().find(|row| {
+            row.start_date
+                .as_deref()
+                .and_then(parse_event_date)
+                .map(|d| d.date_naive())
+                == next_start
+        }) {
+            return Err(AppError::Conflict(format!(
+                "The next edition already exists as event {}",
+                row.id
+            )));
+        }
+
+        self.event_logic.validate_event(&next)?;
+        let new_id = self.events_context.create(&next).await?;
+
+        self.user_collection_logic
+            .event_ownership(new_id, &claims.sub)
+            .await?;
+
+        self.event_logic
+            .publish_change(ScheduleChangeKind::Created, new_id)
+            .await;
+
+        Ok(new_id)
+    }
+
+    ///Adds an event to a series (or takes it out with None). The caller has to
+    ///own the event, and the series through one of its editions, admins bypass both.
+    pub async fn set_event_series(
+        &self,
+        event_id: i64,
+        series_id: Option<i64>,
+        claims: Claims,
+    ) -> Result<(), AppError> {
+        let is_admin = claims.role == "admin" || claims.role == "super_admin";
+        let collection = self.user_collection_logic.get(&claims.sub).await?;
+        if !is_admin && !collection.created_events.contains(&event_id) {
+            return Err(AppError::Unauthorized(
+                "You do not have permission to change this event's series".to_string(),
+            ));
+        }
+
+        if let Some(series_id) = series_id {
+            match self.repository.find_by_id(series_id).await {
+                Ok(_) => {}
+                Err(AppError::NotFound(_)) => {
+                    return Err(AppError::NotFound(format!(
+                        "Event series {} not found",
+                        series_id
+                    )));
+                }
+                Err(e) => return Err(e),
+            }
+
+            if !is_admin {
+                let editions = self.events_context.find_by_series(series_id).await?;
+                let owns_series = editions
+                    .iter()
+                    .any(|row| collection.created_events.contains(&row.id));
+                if !owns_series {
+                    return Err(AppError::Unauthorized(
+                        "You do not have permission to add events to this series".to_string(),
+                    ));
+                }
+            }
+        }
+
+        let updated = self.events_context.set_series(event_id, series_id).await?;
+        if !updated {
+            return Err(AppError::NotFound("Event not found".to_string()));
+        }
+
+        self.event_logic
+            .publish_change(ScheduleChangeKind::Updated, event_id)
+            .await;
+
+        Ok(())
+    }
+
+    fn next_edition(
+        mut event: NomEvent,
+        series_id: i64,
+        user_id: &str,
+    ) -> Result<NomEvent, AppError> {
+        let year = Months::new(12);
+        let shift_failed =
+            || AppError::ValidationError("Event dates can not be moved a year forward".to_string());
+
+        if let Some(start) = event.date_info.start_date {
+            event.date_info.start_date =
+                Some(start.checked_add_months(year).ok_or_else(shift_failed)?);
+            event.name = bump_year(&event.name, start.year());
+        }
+        if let Some(end) = event.date_info.end_date {
+            event.date_info.end_date = Some(end.checked_add_months(year).ok_or_else(shift_failed)?);
+        }
+        // Free text, only shifted when it is a date we understand
+        if let Some(early) = &event.date_info.early_arrival_date
+            && let Some(parsed) = parse_event_date(early)
+        {
+            let shifted = parsed.checked_add_months(year).ok_or_else(shift_failed)?;
+            event.date_info.early_arrival_date = Some(shifted.format("%Y-%m-%d").to_string());
+        }
+
+        event.id = None;
+        event.user_id = Some(user_id.to_string());
+        event.series_id = Some(series_id);
+        event.archive = false;
+
+        Ok(event)
+    }
+
+    fn validate_series(series: &EventSeries) -> Result<(), AppError> {
+        if series.name.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Series name cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+// "Burning Man 2025" -> "Burning Man 2026". Only the last standalone year
+// changes, "Route 2025 Rally 2025" keeps its route and "120250" is no year.
+fn bump_year(name: &str, year: i32) -> String {
+    let year_text = year.to_string();
+    let standalone = name.rmatch_indices(&year_text).find(|(at, _)| {
+        let end = at + year_text.len();
+        !name[..*at].ends_with(|c: char| c.is_ascii_digit())
+            && !name[end..].starts_with(|c: char| c.is_ascii_digit())
+    });
+
+    match standalone {
+        Some((at, _)) => format!(
+            "{}{}{}",
+            &name[..at],
+            year + 1,
+            &name[at + year_text.len()..]
+        ),
+        None => name.to_string(),
+    }
+}
+
+// "Burning Man 2025" -> "Burning Man"
+fn strip_year(name: &str, year: i32) -> String {
+    let stripped = name.replace(&year.to_string(), "");
+    let stripped = stripped.split_whitespace().collect::<Vec<_>>().join(" ");
+    if stripped.is_empty() {
+        name.to_string()
+    } else {
+        stripped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bump_year_changes_only_the_last_standalone_year() {
+        assert_eq!(bump_year("Burning Man 2025", 2025), "Burning Man 2026");
+        assert_eq!(
+            bump_year("Route 2025 Rally 2025", 2025),
+            "Route 2025 Rally 2026"
+        );
+        assert_eq!(bump_year("Lot 120250 Meetup", 2025), "Lot 120250 Meetup");
+        assert_eq!(
+            bump_year("2025 Winter Gathering", 2025),
+            "2026 Winter Gathering"
+        );
+    }
+}
@@ -0,0 +1,254 @@
+// ============================================================================
+// src/logic/mail_logic.rs - Email outbox, background sender and verification
+// ============================================================================
+use crate::context::{EmailOutboxContext, EmailVerificationContext, NotificationContext};
+use crate::errors::AppError;
+use crate::logic::mail_transport::MailTransport;
+use crate::models::email_models::{
+    DigestItem, EmailTemplate, MailSendReport, OutgoingEmail, QueuedEmail,
+};
+use crate::models::notification_models::{
+    NotificationCategory, NotificationChannel, NotificationPreferences,
+};
+use chrono::{Duration, SecondsFormat, Utc};
+use sha2::{Digest, Sha256};
+
+const SEND_BATCH: i64 = 20;
+const MAX_ATTEMPTS: i64 = 8;
+// 1, 2, 4 ... minutes between attempts, never more than 6 hours
+const BACKOFF_BASE_SECS: i64 = 60;
+const BACKOFF_MAX_SECS: i64 = 6 * 60 * 60;
+const VERIFICATION_HOURS: i64 = 24;
+
+pub struct MailLogic {
+    outbox: EmailOutboxContext,
+    verifications: EmailVerificationContext,
+    notifications: NotificationContext,
+    transport: Box<dyn MailTransport>,
+    from: String,
+    base_url: String,
+}
+
+impl MailLogic {
+    pub fn new(
+        outbox: EmailOutboxContext,
+        verifications: EmailVerificationContext,
+        notifications: NotificationContext,
+        transport: Box<dyn MailTransport>,
+        from: String,
+        base_url: String,
+    ) -> Self {
+        Self {
+            outbox,
+            verifications,
+            notifications,
+            transport,
+            from,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn transport_name(&self) -> &'static str {
+        self.transport.name()
+    }
+
+    ///Renders the template and stores it in the outbox, the sender picks it up from there
+    pub async fn queue(&self, to: &str, template: &EmailTemplate) -> Result<i64, AppError> {
+        self.enqueue(&template.queued(to)).await
+    }
+
+    pub async fn enqueue(&self, email: &QueuedEmail) -> Result<i64, AppError> {
+        self.outbox.enqueue(email).await
+    }
+
+    ///One pass of the background sender
+    pub async fn send_due(&self) -> Result<MailSendReport, AppError> {
+        let mut report = MailSendReport::default();
+        let now = Utc::now();
+        let due = self
+            .outbox
+            .find_due(&now.to_rfc3339_opts(SecondsFormat::Secs, true), SEND_BATCH)
+            .await?;
+
+        for row in due {
+            let email = OutgoingEmail {
+                to: row.to_address,
+                subject: row.subject,
+                body: row.body,
+            };
+
+            let Err(e) = self.transport.send(&self.from, &email).await else {
+                self.outbox.mark_sent(row.id).await?;
+                report.sent += 1;
+                continue;
+            };
+
+            let attempts = row.attempts + 1;
+            let error = e.to_string();
+            if attempts >= MAX_ATTEMPTS {
+                tracing::warn!(
+                    "email {} ({}) failed for good after {} attempts: {}",
+                    row.id,
+                    row.message_type,
+                    attempts,
+                    error
+                );
+                self.outbox.mark_failed(row.id, &error).await?;
+                report.failed += 1;
+            } else {
+                let delay = (BACKOFF_BASE_SECS << (attempts - 1)).min(BACKOFF_MAX_SECS);
+                let next_attempt_at =
+                    (now + Duration::seconds(delay)).to_rfc3339_opts(SecondsFormat::Secs, true);
+                self.outbox
+                    .mark_retry(row.id, &next_attempt_at, &error)
+                    .await?;
+                report.retrying += 1;
+            }
+        }
+
+        Ok(report)
+    }
+
+    ///Emails a confirmation link to the address currently on the account
+    pub async fn request_verification(&self, user_id: &str) -> Result<(), AppError> {
+        let (user_name, email, verified) = self
+            .notifications
+            .find_contact(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let email = email
+            .filter(|e| !e.trim().is_empty())
+            .ok_or_else(|| AppError::ValidationError("Add an email address first".to_string()))?;
+        if verified {
+            return Err(AppError::Conflict(
+                "This email address is already verified".to_string(),
+            ));
+        }
+
+        let bytes: [u8; 32] = rand::random();
+        let token = hex::encode(bytes);
+        let expires_at = (Utc::now() + Duration::hours(VERIFICATION_HOURS))
+            .to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        self.verifications
+            .create(&hash_token(&token), user_id, &email, &expires_at)
+            .await?;
+
+        self.queue(
+            &email,
+            &EmailTemplate::EmailVerification {
+                user_name,
+                link: format!("{}/verify-email/{}", self.base_url, token),
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn verify_email(&self, token: &str) -> Result<(), AppError> {
+        let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        let verified = self.verifications.consume(&hash_token(token), &now).await?;
+        if !verified {
+            return Err(AppError::NotFound(
+                "This verification link is invalid or has expired".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    ///Renders an event change notice if the user has a verified address, None when
+    ///there is nobody to send it to. NotificationService queues it with the notification.
+    pub async fn event_changed_email(
+        &self,
+        user_id: &str,
+        title: &str,
+        summary: &str,
+        event_id: Option<i64>,
+    ) -> Result<Option<QueuedEmail>, AppError> {
+        let Some((user_name, Some(email), true)) = self.notifications.find_contact(user_id).await?
+        else {
+            return Ok(None);
+        };
+
+        let template = EmailTemplate::EventChanged {
+            user_name,
+            title: title.to_string(),
+            summary: summary.to_string(),
+            link: event_id.map(|id| format!("{}/event/{}", self.base_url, id)),
+        };
+
+        Ok(Some(template.queued(&email)))
+    }
+
+    ///Queues one digest per user with unread notifications since their last digest.
+    ///Favorite event changes are left out, those are emailed as they happen.
+    pub async fn queue_digests(&self, min_gap: Duration) -> Result<usize, AppError> {
+        let mut queued = 0;
+
+        for user_id in self.notifications.find_users_with_unread().await? {
+            let Some((user_name, Some(email), true)) =
+                self.notifications.find_contact(&user_id).await?
+            else {
+                continue;
+            };
+
+            let preferences = match self.notifications.find_preferences(&user_id).await? {
+                Some(raw) => serde_json::from_str(&raw).unwrap_or_default(),
+                None => NotificationPreferences::default(),
+            };
+
+            let last = self.outbox.last_queued_at(&email, "digest").await?;
+            if let Some(last) = last.as_deref().and_then(parse_timestamp)
+                && Utc::now() - last < min_gap
+            {
+                continue;
+            }
+
+            let items: Vec<DigestItem> = self
+                .notifications
+                .find_unread_since(&user_id, last.as_deref())
+                .await?
+                .into_iter()
+                .filter(|n| {
+                    category_from_str(&n.category).is_some_and(|category| {
+                        category != NotificationCategory::FavoriteEvent
+                            && preferences.allows(category, NotificationChannel::Email)
+                    })
+                })
+                .map(|n| DigestItem {
+                    title: n.title,
+                    body: n.body,
+                })
+                .collect();
+            if items.is_empty() {
+                continue;
+            }
+
+            self.queue(&email, &EmailTemplate::Digest { user_name, items })
+                .await?;
+            queued += 1;
+        }
+
+        Ok(queued)
+    }
+}
+
+fn category_from_str(value: &str) -> Option<NotificationCategory> {
+    serde_json::from_value(serde_json::Value::from(value)).ok()
+}
+
+fn parse_timestamp(value: &str) -> Option<chrono::DateTime<Utc>> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+fn hash_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    hex::encode(hasher.finalize())
+}
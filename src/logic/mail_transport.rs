@@ -0,0 +1,484 @@
+// ============================================================================
+// src/logic/mail_transport.rs - How queued email leaves the building
+// ============================================================================
+use crate::errors::AppError;
+use crate::models::email_models::OutgoingEmail;
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::Utc;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+///Anything that can deliver one rendered message. The outbox sender owns retries,
+///so an implementation only has to report whether this attempt worked.
+#[async_trait]
+pub trait MailTransport: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn send(&self, from: &str, email: &OutgoingEmail) -> Result<(), AppError>;
+}
+
+///Picks the transport from MAIL_TRANSPORT (smtp or file). Defaults to
+///file so local development never sends real email by accident.
+pub fn transport_from_env() -> Result<Box<dyn MailTransport>, AppError> {
+    let kind = std::env::var("MAIL_TRANSPORT").unwrap_or_else(|_| "file".to_string());
+
+    match kind.as_str() {
+        "smtp" => Ok(Box::new(SmtpTransport::from_env()?)),
+        "file" => {
+            let dir = std::env::var("MAIL_FILE_DIR").unwrap_or_else(|_| "mail".to_string());
+            Ok(Box::new(FileTransport::new(dir)))
+        }
+        other => Err(AppError::InternalError(format!(
+            "Unknown MAIL_TRANSPORT {}, expected smtp or file",
+            other
+        ))),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// File: one .eml per message, open them in any mail client
+// ----------------------------------------------------------------------------
+
+pub struct FileTransport {
+    dir: PathBuf,
+}
+
+impl FileTransport {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl MailTransport for FileTransport {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn send(&self, from: &str, email: &OutgoingEmail) -> Result<(), AppError> {
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(mail_error)?;
+
+        let file_name = format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%dT%H%M%S"),
+            uuid::Uuid::new_v4()
+        );
+        tokio::fs::write(self.dir.join(file_name), format_message(from, email))
+            .await
+            .map_err(mail_error)?;
+
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// SMTP: plain, STARTTLS (default) or implicit TLS, with optional AUTH PLAIN
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    None,
+    StartTls,
+    Tls,
+}
+
+pub struct SmtpTransport {
+    host: String,
+    port: u16,
+    security: SmtpSecurity,
+    credentials: Option<(String, String)>,
+    hello_name: String,
+}
+
+impl SmtpTransport {
+    ///SMTP_HOST, SMTP_PORT, SMTP_SECURITY (none, starttls, tls),
+    ///SMTP_USERNAME and SMTP_PASSWORD
+    pub fn from_env() -> Result<Self, AppError> {
+        let host = std::env::var("SMTP_HOST")?;
+        let security = match std::env::var("SMTP_SECURITY").as_deref() {
+            Ok("none") => SmtpSecurity::None,
+            Ok("tls") => SmtpSecurity::Tls,
+            Ok("starttls") | Err(_) => SmtpSecurity::StartTls,
+            Ok(other) => {
+                return Err(AppError::InternalError(format!(
+                    "Unknown SMTP_SECURITY {}, expected none, starttls or tls",
+                    other
+                )));
+            }
+        };
+        let default_port = match security {
+            SmtpSecurity::None => 25,
+            SmtpSecurity::StartTls => 587,
+            SmtpSecurity::Tls => 465,
+        };
+        let port = match std::env::var("SMTP_PORT") {
+            Ok(port) => port
+                .parse()
+                .map_err(|_| AppError::InternalError(format!("Invalid SMTP_PORT {}", port)))?,
+            Err(_) => default_port,
+        };
+        let credentials = match (
+            std::env::var("SMTP_USERNAME"),
+            std::env::var("SMTP_PASSWORD"),
+        ) {
+            (Ok(username), Ok(password)) => Some((username, password)),
+            _ => None,
+        };
+
+        Ok(Self {
+            host,
+            port,
+            security,
+            credentials,
+            hello_name: std::env::var("SMTP_HELLO_NAME").unwrap_or_else(|_| "localhost".into()),
+        })
+    }
+
+    async fn deliver(&self, from: &str, email: &OutgoingEmail) -> Result<(), AppError> {
+        let stream = TcpStream::connect((self.host.as_str(), self.port))
+            .await
+            .map_err(mail_error)?;
+
+        match self.security {
+            SmtpSecurity::None => {
+                let mut session = SmtpSession::new(stream);
+                session.expect(220).await?;
+                session.hello(&self.hello_name).await?;
+                self.transaction(&mut session, from, email).await
+            }
+            SmtpSecurity::Tls => {
+                let mut session = SmtpSession::new(self.tls(stream).await?);
+                session.expect(220).await?;
+                session.hello(&self.hello_name).await?;
+                self.transaction(&mut session, from, email).await
+            }
+            SmtpSecurity::StartTls => {
+                let mut plain = SmtpSession::new(stream);
+                plain.expect(220).await?;
+                plain.hello(&self.hello_name).await?;
+                plain.command("STARTTLS", 220).await?;
+
+                let mut session = SmtpSession::new(self.tls(plain.into_inner()).await?);
+                // capabilities have to be asked for again over TLS
+                session.hello(&self.hello_name).await?;
+                self.transaction(&mut session, from, email).await
+            }
+        }
+    }
+
+    async fn tls(
+        &self,
+        stream: TcpStream,
+    ) -> Result<tokio_native_tls::TlsStream<TcpStream>, AppError> {
+        let connector = tokio_native_tls::native_tls::TlsConnector::new().map_err(mail_error)?;
+        tokio_native_tls::TlsConnector::from(connector)
+            .connect(&self.host, stream)
+            .await
+            .map_err(mail_error)
+    }
+
+    async fn transaction<S>(
+        &self,
+        session: &mut SmtpSession<S>,
+        from: &str,
+        email: &OutgoingEmail,
+    ) -> Result<(), AppError>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        if let Some((username, password)) = &self.credentials {
+            let token = BASE64.encode(format!("\0{}\0{}", username, password));
+            session
+                .command(&format!("AUTH PLAIN {}", token), 235)
+                .await?;
+        }
+
+        session
+            .command(&format!("MAIL FROM:<{}>", address(from)?), 250)
+            .await?;
+        session
+            .command(&format!("RCPT TO:<{}>", address(&email.to)?), 250)
+            .await?;
+        session.command("DATA", 354).await?;
+
+        // dot-stuffing, a line starting with "." would otherwise end the message early
+        let mut data = String::new();
+        for line in format_message(from, email).lines() {
+            if line.starts_with('.') {
+                data.push('.');
+            }
+            data.push_str(line);
+            data.push_str("\r\n");
+        }
+        data.push_str(".\r\n");
+        session.write(&data).await?;
+        session.expect(250).await?;
+
+        // the message is accepted at this point, a failed QUIT changes nothing
+        let _ = session.command("QUIT", 221).await;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MailTransport for SmtpTransport {
+    fn name(&self) -> &'static str {
+        "smtp"
+    }
+
+    async fn send(&self, from: &str, email: &OutgoingEmail) -> Result<(), AppError> {
+        tokio::time::timeout(SMTP_TIMEOUT, self.deliver(from, email))
+            .await
+            .map_err(|_| AppError::InternalError("SMTP server timed out".to_string()))?
+    }
+}
+
+struct SmtpSession<S> {
+    stream: BufReader<S>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> SmtpSession<S> {
+    fn new(stream: S) -> Self {
+        Self {
+            stream: BufReader::new(stream),
+        }
+    }
+
+    fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+
+    async fn hello(&mut self, name: &str) -> Result<(), AppError> {
+        self.command(&format!("EHLO {}", name), 250).await
+    }
+
+    async fn command(&mut self, command: &str, expected: u16) -> Result<(), AppError> {
+        self.write(&format!("{}\r\n", command)).await?;
+        self.expect(expected).await
+    }
+
+    async fn write(&mut self, data: &str) -> Result<(), AppError> {
+        let stream = self.stream.get_mut();
+        stream
+            .write_all(data.as_bytes())
+            .await
+            .map_err(mail_error)?;
+        stream.flush().await.map_err(mail_error)
+    }
+
+    ///Reads a (possibly multi-line) reply and checks its code
+    async fn expect(&mut self, expected: u16) -> Result<(), AppError> {
+        let mut reply = String::new();
+        loop {
+            let mut line = String::new();
+            let read = self.stream.read_line(&mut line).await.map_err(mail_error)?;
+            if read == 0 {
+                return Err(AppError::InternalError(
+                    "SMTP server closed the connection".to_string(),
+                ));
+            }
+            reply.push_str(&line);
+            // "250-..." continues, "250 ..." is the last line
+            if line.as_bytes().get(3) != Some(&b'-') {
+                break;
+            }
+        }
+
+        let code: u16 = reply.get(..3).and_then(|c| c.parse().ok()).unwrap_or(0);
+        if code != expected {
+            return Err(AppError::InternalError(format!(
+                "SMTP server replied {}",
+                reply.trim_end()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+///RFC 5322 message with a base64 body, so any text survives any relay
+fn format_message(from: &str, email: &OutgoingEmail) -> String {
+    let mut message = String::new();
+    message.push_str(&format!("From: {}\r\n", header_value(from)));
+    message.push_str(&format!("To: {}\r\n", header_value(&email.to)));
+    message.push_str(&format!("Subject: {}\r\n", encode_header(&email.subject)));
+    message.push_str(&format!("Date: {}\r\n", Utc::now().to_rfc2822()));
+    message.push_str(&format!(
+        "Message-ID: <{}@nomad-events>\r\n",
+        uuid::Uuid::new_v4()
+    ));
+    message.push_str("MIME-Version: 1.0\r\n");
+    message.push_str("Content-Type: text/plain; charset=utf-8\r\n");
+    message.push_str("Content-Transfer-Encoding: base64\r\n");
+    message.push_str("\r\n");
+
+    let encoded = BASE64.encode(email.body.replace('\n', "\r\n"));
+    for chunk in encoded.as_bytes().chunks(76) {
+        message.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+        message.push_str("\r\n");
+    }
+
+    message
+}
+
+// Line breaks in a header value would let it add headers of its own
+fn header_value(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+
+// RFC 2047 encoded-word for anything that is not plain ASCII
+fn encode_header(value: &str) -> String {
+    let value = header_value(value);
+    if value.is_ascii() {
+        value
+    } else {
+        format!("=?UTF-8?B?{}?=", BASE64.encode(value))
+    }
+}
+
+fn address(value: &str) -> Result<&str, AppError> {
+    if value.is_empty() || value.contains(['<', '>', '\r', '\n', ' ']) {
+        return Err(AppError::ValidationError(format!(
+            "Invalid email address {}",
+            value
+        )));
+    }
+    Ok(value)
+}
+
+fn mail_error(e: impl std::fmt::Display) -> AppError {
+    AppError::InternalError(format!("Mail transport error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    ///Plays the server side of one session: for each step, reads a line and
+    ///replies. A step without a command is the greeting. Returns what the
+    ///client sent in the DATA section.
+    async fn fake_server(
+        listener: TcpListener,
+        script: Vec<(Option<&'static str>, &'static str)>,
+    ) -> String {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut stream = BufReader::new(stream);
+        let mut data = String::new();
+
+        for (command, reply) in script {
+            if let Some(command) = command {
+                let mut line = String::new();
+                stream.read_line(&mut line).await.unwrap();
+                assert_eq!(line, format!("{}\r\n", command));
+
+                if command == "DATA" {
+                    stream
+                        .get_mut()
+                        .write_all(b"354 go ahead\r\n")
+                        .await
+                        .unwrap();
+                    loop {
+                        let mut line = String::new();
+                        stream.read_line(&mut line).await.unwrap();
+                        if line == ".\r\n" {
+                            break;
+                        }
+                        data.push_str(&line);
+                    }
+                }
+            }
+            stream.get_mut().write_all(reply.as_bytes()).await.unwrap();
+        }
+
+        data
+    }
+
+    fn transport(port: u16, credentials: Option<(String, String)>) -> SmtpTransport {
+        SmtpTransport {
+            host: "127.0.0.1".to_string(),
+            port,
+            security: SmtpSecurity::None,
+            credentials,
+            hello_name: "test.local".to_string(),
+        }
+    }
+
+    fn email(body: &str) -> OutgoingEmail {
+        OutgoingEmail {
+            to: "camper@example.com".to_string(),
+            subject: "Hello".to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn smtp_session_delivers_with_auth() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(fake_server(
+            listener,
+            vec![
+                (None, "220 fake ESMTP\r\n"),
+                (Some("EHLO test.local"), "250-fake\r\n250 AUTH PLAIN\r\n"),
+                // "\0user\0secret"
+                (Some("AUTH PLAIN AHVzZXIAc2VjcmV0"), "235 ok\r\n"),
+                (Some("MAIL FROM:<events@example.com>"), "250 ok\r\n"),
+                (Some("RCPT TO:<camper@example.com>"), "250 ok\r\n"),
+                (Some("DATA"), "250 queued\r\n"),
+                (Some("QUIT"), "221 bye\r\n"),
+            ],
+        ));
+
+        transport(port, Some(("user".to_string(), "secret".to_string())))
+            .send("events@example.com", &email("See you there"))
+            .await
+            .unwrap();
+
+        let data = server.await.unwrap();
+        assert!(data.contains("To: camper@example.com\r\n"));
+        assert!(data.contains("Subject: Hello\r\n"));
+        assert!(data.contains(&format!("{}\r\n", BASE64.encode("See you there"))));
+    }
+
+    #[tokio::test]
+    async fn smtp_session_fails_on_rejected_recipient() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(fake_server(
+            listener,
+            vec![
+                (None, "220 fake ESMTP\r\n"),
+                (Some("EHLO test.local"), "250 fake\r\n"),
+                (Some("MAIL FROM:<events@example.com>"), "250 ok\r\n"),
+                (Some("RCPT TO:<camper@example.com>"), "550 no such user\r\n"),
+            ],
+        ));
+
+        let result = transport(port, None)
+            .send("events@example.com", &email("See you there"))
+            .await;
+
+        server.await.unwrap();
+        let error = format!("{:?}", result.unwrap_err());
+        assert!(error.contains("550 no such user"), "{}", error);
+    }
+
+    #[test]
+    fn header_values_cannot_add_headers() {
+        assert_eq!(header_value("a\r\nBcc: x"), "a  Bcc: x");
+        assert_eq!(
+            encode_header("Café"),
+            format!("=?UTF-8?B?{}?=", BASE64.encode("Café"))
+        );
+    }
+}
@@ -1,46 +1,88 @@
 // ============================================================================
 // src/logic/microevent_logic.rs - Business Logic Layer
 // ============================================================================
-use crate::errors::AppError;
+use crate::errors::{AppError, FieldError};
 //use crate::models::dto::MicroeventResponse;
-use crate::context::MicroeventContext;
-use crate::logic::{UserCollectionLogic, user_collection_logic};
-use crate::models::microevents_models::Microevent;
+use crate::context::{EventContext, MicroeventContext, MicroeventTrackContext, VenueAreaContext};
+use crate::logic::recurrence::RecurrenceRule;
+use crate::logic::{
+    ChangeFeed, NotificationService, RsvpLogic, UserCollectionLogic, WebhookLogic,
+    user_collection_logic,
+};
+use crate::models::database_models::UserEventDataRow;
+use crate::models::live_models::{ScheduleChange, ScheduleChangeKind};
+use crate::models::microevents_models::{
+    ApprovalStatus, HappeningNow, Microevent, MicroeventFilterParams, NowEntry, NowParams,
+};
+use crate::models::notification_models::NotificationCategory;
 use crate::models::user::Claims;
+use crate::models::webhook_models::{MICROEVENT_CREATED, MICROEVENT_DELETED, MICROEVENT_UPDATED};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
 use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 pub struct MicroeventLogic {
     context: MicroeventContext,
+    events: EventContext,
+    areas: VenueAreaContext,
+    tracks: MicroeventTrackContext,
     user_collection_logic: Arc<UserCollectionLogic>,
+    webhook_logic: Arc<WebhookLogic>,
+    change_feed: Arc<ChangeFeed>,
+    rsvp_logic: Arc<RsvpLogic>,
+    notification_service: Arc<NotificationService>,
 }
 
 impl MicroeventLogic {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         context: MicroeventContext,
+        events: EventContext,
+        areas: VenueAreaContext,
+        tracks: MicroeventTrackContext,
         user_collection_logic: Arc<UserCollectionLogic>,
+        webhook_logic: Arc<WebhookLogic>,
+        change_feed: Arc<ChangeFeed>,
+        rsvp_logic: Arc<RsvpLogic>,
+        notification_service: Arc<NotificationService>,
     ) -> Self {
         Self {
             context,
+            events,
+            areas,
+            tracks,
             user_collection_logic,
+            webhook_logic,
+            change_feed,
+            rsvp_logic,
+            notification_service,
         }
     }
 
-    pub async fn get_all(&self) -> Result<Vec<Microevent>, AppError> {
+    pub async fn get_all(&self, claims: &Claims) -> Result<Vec<Microevent>, AppError> {
         let rows = self.context.find_all().await?;
+        let collection = self.user_collection_logic.get(&claims.sub).await?;
 
         let events: Vec<Microevent> = rows
             .into_iter()
             //.filter_map(|row| Microevent::from_row(row).ok())
+            .filter(|m| visible_to(m, claims, &collection))
             .collect();
 
         Ok(events)
     }
 
-    pub async fn get(&self, id: i64) -> Result<Microevent, AppError> {
+    pub async fn get(&self, id: i64, claims: &Claims) -> Result<Microevent, AppError> {
         let row = self.context.find_by_id(id).await?;
         //let event = Microevent::from_row(row)?;
         //Ok(event)
+        if row.approval_status != ApprovalStatus::Approved {
+            let collection = self.user_collection_logic.get(&claims.sub).await?;
+            if !visible_to(&row, claims, &collection) {
+                return Err(AppError::NotFound("Microevent not found".to_string()));
+            }
+        }
         Ok(row)
     }
 
@@ -49,14 +91,54 @@ impl MicroeventLogic {
         Ok(row)
     }
 
-    pub async fn get_by_event(&self, id: i64) -> Result<Vec<Microevent>, AppError> {
+    pub async fn get_by_event(
+        &self,
+        id: i64,
+        filter: &MicroeventFilterParams,
+        claims: &Claims,
+    ) -> Result<Vec<Microevent>, AppError> {
+        let tracks = filter.track_filter()?;
+
         println!("About to make row request");
         let rows = self.context.find_by_event(id).await?;
         println!("number of rows found: {}", rows.iter().count());
 
+        // archived and unreviewed microevents are only shown to the people
+        // behind them, the collection is only looked up when it matters
+        let needs_access = filter.include_archived
+            || rows
+                .iter()
+                .any(|m| m.approval_status != ApprovalStatus::Approved);
+        let access = if needs_access {
+            let is_admin = claims.role == "admin" || claims.role == "super_admin";
+            let collection = self.user_collection_logic.get(&claims.sub).await?;
+            Some((
+                is_admin || collection.created_events.contains(&id),
+                collection.created_microevents,
+            ))
+        } else {
+            None
+        };
+        let behind = |m: &Microevent| match &access {
+            Some((true, _)) => true,
+            Some((false, own)) => {
+                own.contains(&m.id)
+                    || m.recurrence_parent_id
+                        .is_some_and(|parent_id| own.contains(&parent_id))
+            }
+            None => false,
+        };
+        let show = |m: &Microevent| {
+            (!m.cancelled || filter.include_cancelled)
+                && (!m.archive || (filter.include_archived && behind(m)))
+                && (m.approval_status == ApprovalStatus::Approved || behind(m))
+        };
+
         let events: Vec<Microevent> = rows
             .into_iter()
             //.filter_map(|row| Microevent::from_row(row).ok())
+            .filter(|m| tracks.as_ref().is_none_or(|t| t.contains(&m.track_id)))
+            .filter(show)
             .collect();
 
         //println!("events: {}", events.iter().map(|res| res.to_string())
@@ -66,9 +148,85 @@ impl MicroeventLogic {
         Ok(events)
     }
 
+    ///What is on at the event right now and what starts within the window,
+    ///with local times in the event's time zone
+    pub async fn get_now(
+        &self,
+        event_id: i64,
+        params: &NowParams,
+        now: DateTime<Utc>,
+    ) -> Result<HappeningNow, AppError> {
+        let window = params.window()?;
+        let tz = self.event_tz(event_id).await?;
+        let horizon = now + window;
+
+        let mut happening_now = Vec::new();
+        let mut up_next = Vec::new();
+        let mut refresh_at: Option<DateTime<Utc>> = None;
+        let mut mark = |at: DateTime<Utc>| {
+            refresh_at = Some(refresh_at.map_or(at, |current| current.min(at)));
+        };
+
+        // sorted by start, so everything after the first one past the
+        // horizon is too
+        for microevent in self.context.find_unfinished(event_id, now).await? {
+            let Some(start) = microevent.start_time else {
+                continue;
+            };
+
+            let list = if start <= now {
+                // unfinished and started means it has an end
+                if let Some(end) = microevent.end_time {
+                    mark(end);
+                }
+                &mut happening_now
+            } else if start <= horizon {
+                mark(start);
+                &mut up_next
+            } else {
+                mark(start - window);
+                break;
+            };
+
+            list.push(NowEntry {
+                local_start: microevent
+                    .start_time
+                    .map(|t| t.with_timezone(&tz).fixed_offset()),
+                local_end: microevent
+                    .end_time
+                    .map(|t| t.with_timezone(&tz).fixed_offset()),
+                microevent,
+            });
+        }
+
+        Ok(HappeningNow {
+            event_id,
+            time_zone: tz.name().to_string(),
+            now: now.with_timezone(&tz).fixed_offset(),
+            window_minutes: window.num_minutes(),
+            happening_now,
+            up_next,
+            refresh_at,
+        })
+    }
+
     //the userid should have been added in the last round but it still needs to be added to the user collection
-    pub async fn create(&self, event: Microevent) -> Result<i64, AppError> {
-        self.validate_event(&event)?;
+    pub async fn create(&self, mut event: Microevent, claims: &Claims) -> Result<i64, AppError> {
+        // a new microevent always starts a series of its own, never joins one
+        event.recurrence_rule = event.recurrence_rule.filter(|rule| !rule.trim().is_empty());
+        event.recurrence_parent_id = None;
+        event.detached = false;
+        event.cancelled = false;
+
+        self.validate_event(&event).await?;
+        event.occurrence_date = recurrence_date(&event, self.event_tz(event.event_id).await?);
+
+        event.review_note = None;
+        event.approval_status = if self.needs_approval(event.event_id, claims).await? {
+            ApprovalStatus::Pending
+        } else {
+            ApprovalStatus::Approved
+        };
 
         //get the user id out of the model
         let user_id = &event.user_id;
@@ -77,54 +235,108 @@ impl MicroeventLogic {
             .microevent_ownership(id, user_id)
             .await?;
 
+        self.publish_change(ScheduleChangeKind::Created, id, event.event_id)
+            .await;
+
+        if event.recurrence_rule.is_some() {
+            self.expand_series(id).await?;
+        }
+
+        if event.approval_status == ApprovalStatus::Pending {
+            self.notify_event_owner(&event).await;
+        }
+
         Ok(id)
     }
 
-    pub async fn update(&self, id: i64, event: Microevent, claims: Claims) -> Result<(), AppError> {
+    ///On the first occurrence of a series this changes the whole series, on
+    ///any other occurrence only that one, which is then detached from the series
+    pub async fn update(
+        &self,
+        id: i64,
+        mut event: Microevent,
+        claims: Claims,
+    ) -> Result<(), AppError> {
+        let stored = self.context.find_by_id(id).await?;
+        self.check_owner(&stored, &claims).await?;
+
+        // the series bookkeeping is ours, not the client's, and so is the
+        // owner, the occurrences are handed to whoever owns the first one
+        event.user_id = stored.user_id.clone();
+        event.recurrence_rule = event.recurrence_rule.filter(|rule| !rule.trim().is_empty());
+        event.recurrence_parent_id = stored.recurrence_parent_id;
+        event.cancelled = stored.cancelled;
+        event.detached = event.recurrence_parent_id.is_some();
+
         // Business logic: validate event data
-        self.validate_event(&event)?;
+        self.validate_event(&event).await?;
 
-        // Check if user is admin or superadmin (bypass ownership check)
-        let is_admin = claims.role == "admin" || claims.role == "super_admin";
+        event.occurrence_date = if event.detached {
+            stored.occurrence_date
+        } else {
+            recurrence_date(&event, self.event_tz(event.event_id).await?)
+        };
 
-        if !is_admin {
-            // Check if the user is the owner (correct Id is listed in their usercollection)
-            let collection = self.user_collection_logic.get(&claims.sub).await?;
+        let updated = self.context.update(id, &event).await?;
 
-            // Check if this id is part of the user's created microevents
-            let is_owner = collection.created_microevents.contains(&id);
+        if !updated {
+            return Err(AppError::NotFound("Microevent not found".to_string()));
+        }
 
-            if !is_owner {
-                return Err(AppError::Unauthorized(
-                    "You do not have permission to update this microevent".to_string(),
-                ));
+        // fixing a rejected microevent, or moving it to another event, puts
+        // it in front of that event's owner again
+        if stored.approval_status == ApprovalStatus::Rejected || event.event_id != stored.event_id {
+            let status = if self.needs_approval(event.event_id, &claims).await? {
+                ApprovalStatus::Pending
+            } else {
+                ApprovalStatus::Approved
+            };
+            if status != stored.approval_status {
+                self.set_series_approval(&stored, status, None).await?;
+                if status == ApprovalStatus::Pending {
+                    self.notify_event_owner(&event).await;
+                }
             }
         }
 
-        let updated = self.context.update(id, &event).await?;
+        // archiving is announced as its own kind of change
+        let change = if event.archive && !stored.archive {
+            ScheduleChangeKind::Archived
+        } else {
+            ScheduleChangeKind::Updated
+        };
+        self.publish_change(change, id, event.event_id).await;
 
-        if !updated {
-            return Err(AppError::NotFound("Microevent not found".to_string()));
+        // more seats move people up from the waitlist
+        if event.capacity != stored.capacity {
+            self.rsvp_logic.fill_open_seats(id).await?;
+        }
+
+        if event.recurrence_parent_id.is_none()
+            && (event.recurrence_rule.is_some() || stored.recurrence_rule.is_some())
+        {
+            self.expand_series(id).await?;
         }
 
         Ok(())
     }
 
+    ///Deleting the first occurrence deletes the whole series. Other occurrences
+    ///are cancelled instead, deleting them would only bring them back the next
+    ///time the series changes.
     pub async fn delete(&self, id: i64, claims: Claims) -> Result<(), AppError> {
-        // Check if user is admin or superadmin (bypass ownership check)
-        let is_admin = claims.role == "admin" || claims.role == "super_admin";
-        if !is_admin {
-            // Check if the user is the owner (correct Id is listed in their usercollection)
-            let collection = self.user_collection_logic.get(&claims.sub).await?;
+        let stored = self.context.find_by_id(id).await?;
+        self.check_owner(&stored, &claims).await?;
 
-            // Check if this id is part of the user's created microevents
-            let is_owner = collection.created_microevents.contains(&id);
+        if let Some(parent_id) = stored.recurrence_parent_id {
+            return Err(AppError::BadRequest(format!(
+                "Microevent {} is one occurrence of a series, cancel it instead or delete the series (microevent {})",
+                id, parent_id
+            )));
+        }
 
-            if !is_owner {
-                return Err(AppError::Unauthorized(
-                    "You do not have permission to update this microevent".to_string(),
-                ));
-            }
+        for occurrence in self.context.find_occurrences(id).await? {
+            self.remove(&occurrence).await?;
         }
 
         let deleted = self.context.delete(id).await?;
@@ -132,40 +344,607 @@ impl MicroeventLogic {
         if !deleted {
             return Err(AppError::NotFound("Event not found".to_string()));
         } else {
+            self.publish_change(ScheduleChangeKind::Deleted, id, stored.event_id)
+                .await;
+
             //send this data to the usercollection
             self.user_collection_logic
-                .remove_microevent_ownership(id, &claims.sub)
+                .remove_microevent_ownership(id, &stored.user_id)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    ///Calls off (or brings back) one occurrence, or a one-off microevent.
+    ///The row stays so schedules can show it as cancelled.
+    pub async fn set_cancelled(
+        &self,
+        id: i64,
+        cancelled: bool,
+        claims: Claims,
+    ) -> Result<(), AppError> {
+        let stored = self.context.find_by_id(id).await?;
+        self.check_owner(&stored, &claims).await?;
+
+        let updated = self.context.set_cancelled(id, cancelled).await?;
+        if !updated {
+            return Err(AppError::NotFound("Microevent not found".to_string()));
+        }
+
+        self.publish_change(ScheduleChangeKind::Updated, id, stored.event_id)
+            .await;
+
+        Ok(())
+    }
+
+    ///Archiving hides a microevent from schedules without deleting it, same
+    ///ownership rules as delete. On the first occurrence of a series it
+    ///applies to the whole series, on any other only to that one.
+    pub async fn set_archived(
+        &self,
+        id: i64,
+        archived: bool,
+        claims: Claims,
+    ) -> Result<(), AppError> {
+        let stored = self.context.find_by_id(id).await?;
+        self.check_owner(&stored, &claims).await?;
+
+        let series = stored.recurrence_parent_id.is_none() && stored.recurrence_rule.is_some();
+        let mut targets = vec![stored];
+        if series {
+            targets.extend(self.context.find_occurrences(id).await?);
+        }
+
+        for microevent in targets {
+            if microevent.archive == archived {
+                continue;
+            }
+
+            let updated = if archived {
+                self.context.archive(microevent.id).await?
+            } else {
+                self.context.unarchive(microevent.id).await?
+            };
+            if !updated {
+                return Err(AppError::NotFound("Microevent not found".to_string()));
+            }
+
+            let change = if archived {
+                ScheduleChangeKind::Archived
+            } else {
+                ScheduleChangeKind::Updated
+            };
+            self.publish_change(change, microevent.id, microevent.event_id)
+                .await;
+        }
+
+        Ok(())
+    }
+
+    ///The event owner's review queue: series show up once, as their first occurrence
+    pub async fn get_pending(
+        &self,
+        event_id: i64,
+        claims: &Claims,
+    ) -> Result<Vec<Microevent>, AppError> {
+        if !self.manages_event(event_id, claims).await? {
+            return Err(AppError::Unauthorized(
+                "Only the event owner can review its microevents".to_string(),
+            ));
+        }
+
+        let pending = self
+            .context
+            .find_by_event(event_id)
+            .await?
+            .into_iter()
+            .filter(|m| {
+                m.approval_status == ApprovalStatus::Pending && m.recurrence_parent_id.is_none()
+            })
+            .collect();
+
+        Ok(pending)
+    }
+
+    ///Approves or rejects a pending microevent (a whole series when given its
+    ///first occurrence) and lets the submitter know
+    pub async fn review(
+        &self,
+        id: i64,
+        approve: bool,
+        note: Option<String>,
+        claims: Claims,
+    ) -> Result<(), AppError> {
+        let stored = self.context.find_by_id(id).await?;
+        if !self.manages_event(stored.event_id, &claims).await? {
+            return Err(AppError::Unauthorized(
+                "Only the event owner can review its microevents".to_string(),
+            ));
+        }
+        if let Some(parent_id) = stored.recurrence_parent_id {
+            return Err(AppError::BadRequest(format!(
+                "Microevent {} is one occurrence of a series, review the series (microevent {})",
+                id, parent_id
+            )));
+        }
+        if stored.approval_status != ApprovalStatus::Pending {
+            return Err(AppError::BadRequest(format!(
+                "Microevent {} is not waiting for review",
+                id
+            )));
+        }
+
+        let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+        let (status, title, body) = if approve {
+            (
+                ApprovalStatus::Approved,
+                "Your microevent was approved",
+                format!("\"{}\" is now on the schedule.", stored.name),
+            )
+        } else {
+            (
+                ApprovalStatus::Rejected,
+                "Your microevent was not approved",
+                match &note {
+                    Some(note) => format!(
+                        "\"{}\" was not added to the schedule: {}",
+                        stored.name, note
+                    ),
+                    None => format!("\"{}\" was not added to the schedule.", stored.name),
+                },
+            )
+        };
+
+        self.set_series_approval(&stored, status, note.as_deref())
+            .await?;
+
+        // the decision is made, a failed notification only gets logged
+        if let Err(e) = self
+            .notification_service
+            .notify(
+                &stored.user_id,
+                NotificationCategory::System,
+                title,
+                &body,
+                Some(stored.event_id),
+            )
+            .await
+        {
+            tracing::warn!("could not notify submitter of microevent {}: {}", id, e);
+        }
+
+        Ok(())
+    }
+
+    ///Brings the occurrences in line with the first one's rule and details.
+    ///Occurrences are matched by date so their ids (and everyone's saves)
+    ///survive edits. Detached and cancelled ones keep their own state.
+    async fn expand_series(&self, parent_id: i64) -> Result<(), AppError> {
+        let parent = self.context.find_by_id(parent_id).await?;
+        let existing = self.context.find_occurrences(parent_id).await?;
+
+        let event = self.events.find_stored(parent.event_id).await?;
+        let tz = self.event_tz(parent.event_id).await?;
+
+        let mut slots: Vec<DateTime<Utc>> = match (&parent.recurrence_rule, parent.start_time) {
+            (Some(rule), Some(start)) => {
+                let rule = RecurrenceRule::parse(rule).map_err(AppError::ValidationError)?;
+                // validate_event turns these away, this is for events edited since
+                let (_, closes) = event.date_info.attendance_window(tz).ok_or_else(|| {
+                    AppError::ValidationError(
+                        "The event needs a start date before microevents can repeat".to_string(),
+                    )
+                })?;
+                rule.occurrences(start, closes, tz)
+            }
+            _ => Vec::new(),
+        };
+        // the first slot is the parent itself
+        if !slots.is_empty() {
+            slots.remove(0);
+        }
+        let duration = match (parent.start_time, parent.end_time) {
+            (Some(start), Some(end)) => Some(end - start),
+            _ => None,
+        };
+
+        for occurrence in existing {
+            let slot = slots
+                .iter()
+                .position(|slot| {
+                    Some(slot.with_timezone(&tz).date_naive()) == occurrence.occurrence_date
+                })
+                .map(|pos| slots.remove(pos));
+
+            match slot {
+                Some(_) if occurrence.detached => {}
+                Some(start) => {
+                    let mut updated = occurrence_of(&parent, start, duration, tz);
+                    updated.cancelled = occurrence.cancelled;
+                    self.context.update(occurrence.id, &updated).await?;
+                    if updated.capacity != occurrence.capacity {
+                        self.rsvp_logic.fill_open_seats(occurrence.id).await?;
+                    }
+                    self.publish_change(
+                        ScheduleChangeKind::Updated,
+                        occurrence.id,
+                        parent.event_id,
+                    )
+                    .await;
+                }
+                // a hand-edited occurrence outlives its date leaving the rule
+                None if occurrence.detached => {}
+                None => self.remove(&occurrence).await?,
+            }
+        }
+
+        let mut created = Vec::new();
+        for start in slots {
+            let id = self
+                .context
+                .create(&occurrence_of(&parent, start, duration, tz))
+                .await?;
+            self.publish_change(ScheduleChangeKind::Created, id, parent.event_id)
+                .await;
+            created.push(id);
+        }
+        if !created.is_empty() {
+            self.user_collection_logic
+                .microevents_ownership(&created, &parent.user_id)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn remove(&self, microevent: &Microevent) -> Result<(), AppError> {
+        if self.context.delete(microevent.id).await? {
+            self.publish_change(
+                ScheduleChangeKind::Deleted,
+                microevent.id,
+                microevent.event_id,
+            )
+            .await;
+            self.user_collection_logic
+                .remove_microevent_ownership(microevent.id, &microevent.user_id)
                 .await?;
         }
+        Ok(())
+    }
+
+    ///From the time_zone column, so polling endpoints don't read event_data.
+    ///UTC when it is not set (or not a zone chrono-tz knows).
+    async fn event_tz(&self, event_id: i64) -> Result<Tz, AppError> {
+        let time_zone = self.events.find_time_zone(event_id).await?;
+        Ok(time_zone.and_then(|tz| tz.parse().ok()).unwrap_or(Tz::UTC))
+    }
+
+    async fn check_owner(&self, microevent: &Microevent, claims: &Claims) -> Result<(), AppError> {
+        // Check if user is admin or superadmin (bypass ownership check)
+        let is_admin = claims.role == "admin" || claims.role == "super_admin";
+        if is_admin {
+            return Ok(());
+        }
+
+        // Check if the user is the owner (correct Id is listed in their usercollection)
+        let collection = self.user_collection_logic.get(&claims.sub).await?;
+        let is_owner = collection.created_microevents.contains(&microevent.id)
+            // Owners of a series own every occurrence in it
+            || microevent
+                .recurrence_parent_id
+                .is_some_and(|parent_id| collection.created_microevents.contains(&parent_id));
+
+        if !is_owner {
+            return Err(AppError::Unauthorized(
+                "You do not have permission to update this microevent".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    ///Approval is only needed on events that ask for it, and never from the
+    ///event's owner or an admin
+    async fn needs_approval(&self, event_id: i64, claims: &Claims) -> Result<bool, AppError> {
+        let required = match self.events.find_require_approval(event_id).await {
+            Ok(required) => required,
+            // validate_event reports the missing event
+            Err(AppError::NotFound(_)) => return Ok(false),
+            Err(e) => return Err(e),
+        };
+
+        Ok(required && !self.manages_event(event_id, claims).await?)
+    }
+
+    async fn manages_event(&self, event_id: i64, claims: &Claims) -> Result<bool, AppError> {
+        let is_admin = claims.role == "admin" || claims.role == "super_admin";
+        if is_admin {
+            return Ok(true);
+        }
+
+        let collection = self.user_collection_logic.get(&claims.sub).await?;
+        Ok(collection.created_events.contains(&event_id))
+    }
+
+    ///Sets the outcome on the microevent, and on its occurrences when it is
+    ///the first of a series. Schedules only see approved ones, so coming onto
+    ///or off the schedule is announced as created or deleted.
+    async fn set_series_approval(
+        &self,
+        microevent: &Microevent,
+        status: ApprovalStatus,
+        note: Option<&str>,
+    ) -> Result<(), AppError> {
+        let mut targets = vec![(microevent.id, microevent.approval_status)];
+        if microevent.recurrence_parent_id.is_none() && microevent.recurrence_rule.is_some() {
+            targets.extend(
+                self.context
+                    .find_occurrences(microevent.id)
+                    .await?
+                    .into_iter()
+                    .map(|m| (m.id, m.approval_status)),
+            );
+        }
+
+        for (id, previous) in targets {
+            self.context.set_approval(id, status, note).await?;
+
+            if status == ApprovalStatus::Approved && previous != ApprovalStatus::Approved {
+                self.publish_change(ScheduleChangeKind::Created, id, microevent.event_id)
+                    .await;
+            } else if status != ApprovalStatus::Approved && previous == ApprovalStatus::Approved {
+                self.publish_change(ScheduleChangeKind::Deleted, id, microevent.event_id)
+                    .await;
+            }
+        }
 
         Ok(())
     }
 
+    ///Tells the event's owner there is something new in the review queue
+    async fn notify_event_owner(&self, microevent: &Microevent) {
+        let owner = match self.events.find_stored(microevent.event_id).await {
+            Ok(event) => event.user_id,
+            Err(e) => {
+                tracing::warn!("could not load event {}: {}", microevent.event_id, e);
+                return;
+            }
+        };
+        let Some(owner) = owner else {
+            return;
+        };
+
+        if let Err(e) = self
+            .notification_service
+            .notify(
+                &owner,
+                NotificationCategory::System,
+                "A microevent is waiting for your review",
+                &format!("\"{}\" was submitted to your event.", microevent.name),
+                Some(microevent.event_id),
+            )
+            .await
+        {
+            tracing::warn!(
+                "could not notify owner of event {}: {}",
+                microevent.event_id,
+                e
+            );
+        }
+    }
+
+    ///Announces a change that has already been written, to webhooks and live
+    ///subscribers. Failures are logged, the write itself stands.
+    async fn publish_change(&self, change: ScheduleChangeKind, id: i64, event_id: i64) {
+        // the stored row is what subscribers should see, and its event_id wins
+        let (event_id, microevent) = if change == ScheduleChangeKind::Deleted {
+            (event_id, None)
+        } else {
+            match self.context.find_by_id(id).await {
+                // not on the schedule until approved, set_series_approval announces it then
+                Ok(microevent) if microevent.approval_status != ApprovalStatus::Approved => return,
+                Ok(microevent) => (microevent.event_id, serde_json::to_value(&microevent).ok()),
+                Err(_) => (event_id, None),
+            }
+        };
+
+        let webhook_type = match change {
+            ScheduleChangeKind::Created => MICROEVENT_CREATED,
+            ScheduleChangeKind::Updated | ScheduleChangeKind::Archived => MICROEVENT_UPDATED,
+            ScheduleChangeKind::Deleted => MICROEVENT_DELETED,
+        };
+        let data = microevent
+            .clone()
+            .unwrap_or_else(|| json!({ "id": id, "event_id": event_id }));
+        if let Err(e) = self
+            .webhook_logic
+            .emit(webhook_type, event_id, Some(id), data)
+            .await
+        {
+            tracing::warn!("microevent {} {} webhooks failed: {}", id, webhook_type, e);
+        }
+
+        self.change_feed.publish(ScheduleChange::for_microevent(
+            event_id, id, change, microevent,
+        ));
+    }
+
     // Private business logic methods
-    fn validate_event(&self, event: &Microevent) -> Result<(), AppError> {
+    async fn validate_event(&self, event: &Microevent) -> Result<(), AppError> {
+        let mut errors = Vec::new();
+
         if event.name.trim().is_empty() {
-            return Err(AppError::ValidationError(
-                "Event name cannot be empty".to_string(),
+            errors.push(FieldError::new("name", "Event name cannot be empty"));
+        }
+
+        if let (Some(start), Some(end)) = (event.start_time, event.end_time)
+            && end < start
+        {
+            errors.push(FieldError::new(
+                "end_time",
+                "End time cannot be before start time",
             ));
         }
-        // Validate dates
-        //if let (Some(start), Some(end)) = (event.start_time, event.end_time) {
-        //if end < start {
-        //return Err(AppError::ValidationError(
-        //"End date cannot be before start date".to_string(),
-        //));
-        //}
-        //}
-        //if (event.date_info.start_date != Option::None) {
-        //if let Some(end_date) = event.date_info.end_date {
-        //if end_date < event.date_info.start_date.expect("REASON") {
-        //return Err(AppError::ValidationError(
-        //"End date cannot be before start date".to_string(),
-        //));
-        //}
-        //}
-        //}
 
-        Ok(())
+        if event.capacity.is_some_and(|capacity| capacity < 1) {
+            errors.push(FieldError::new(
+                "capacity",
+                "Capacity must be at least 1, leave it out for unlimited seats",
+            ));
+        }
+
+        match self.events.find_stored(event.event_id).await {
+            Ok(parent) => {
+                if event.recurrence_rule.is_some()
+                    && event.recurrence_parent_id.is_none()
+                    && parent.date_info.start_date.is_none()
+                {
+                    errors.push(FieldError::new(
+                        "recurrence_rule",
+                        "The event needs a start date before microevents can repeat",
+                    ));
+                }
+
+                // Parents without a start date have no window to hold it to
+                let tz = self.event_tz(event.event_id).await?;
+                if let Some((opens, closes)) = parent.date_info.attendance_window(tz) {
+                    let outside = |time: DateTime<Utc>| time < opens || time >= closes;
+                    let message = format!(
+                        "Must fall within {} to {}, the event's dates including early arrival and late departure",
+                        opens.with_timezone(&tz).format("%Y-%m-%d"),
+                        (closes.with_timezone(&tz) - chrono::Duration::days(1)).format("%Y-%m-%d")
+                    );
+
+                    if event.start_time.is_some_and(outside) {
+                        errors.push(FieldError::new("start_time", message.clone()));
+                    }
+                    if event
+                        .end_time
+                        .is_some_and(|end| end > closes || end <= opens)
+                    {
+                        errors.push(FieldError::new("end_time", message));
+                    }
+                }
+            }
+            Err(AppError::NotFound(_)) => {
+                errors.push(FieldError::new(
+                    "event_id",
+                    format!("Event {} does not exist", event.event_id),
+                ));
+            }
+            // an unreadable legacy blob only costs the date checks, see scan_integrity
+            Err(AppError::SerializationError(e)) => {
+                tracing::warn!(
+                    "event {} data unreadable, microevent dates not checked: {}",
+                    event.event_id,
+                    e
+                );
+            }
+            Err(e) => return Err(e),
+        }
+
+        if let Some(area_id) = event.area_id {
+            match self.areas.find_by_id(area_id).await {
+                Ok(area) if area.event_id == event.event_id => {}
+                Ok(_) | Err(AppError::NotFound(_)) => {
+                    errors.push(FieldError::new(
+                        "area_id",
+                        format!("Area {} is not part of event {}", area_id, event.event_id),
+                    ));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        if let Some(track_id) = event.track_id {
+            match self.tracks.find_by_id(track_id).await {
+                Ok(track) if track.event_id == event.event_id => {}
+                Ok(_) | Err(AppError::NotFound(_)) => {
+                    errors.push(FieldError::new(
+                        "track_id",
+                        format!("Track {} is not part of event {}", track_id, event.event_id),
+                    ));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        if let Some(rule) = &event.recurrence_rule {
+            if event.recurrence_parent_id.is_some() {
+                errors.push(FieldError::new(
+                    "recurrence_rule",
+                    "Change the rule on the first occurrence of the series",
+                ));
+            } else if let Err(e) = RecurrenceRule::parse(rule) {
+                errors.push(FieldError::new("recurrence_rule", e));
+            } else if event.start_time.is_none() {
+                errors.push(FieldError::new(
+                    "start_time",
+                    "A repeating microevent needs a start time",
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::InvalidFields(errors))
+        }
+    }
+}
+
+// Pending and rejected submissions are only shown to the people behind them:
+// the submitter, the event's owner and admins
+fn visible_to(microevent: &Microevent, claims: &Claims, collection: &UserEventDataRow) -> bool {
+    let own = &collection.created_microevents;
+    microevent.approval_status == ApprovalStatus::Approved
+        || claims.role == "admin"
+        || claims.role == "super_admin"
+        || collection.created_events.contains(&microevent.event_id)
+        || own.contains(&microevent.id)
+        || microevent
+            .recurrence_parent_id
+            .is_some_and(|parent_id| own.contains(&parent_id))
+}
+
+// The first occurrence's date in the event's time zone, for microevents that repeat
+fn recurrence_date(event: &Microevent, tz: Tz) -> Option<NaiveDate> {
+    event
+        .recurrence_rule
+        .as_ref()
+        .and(event.start_time)
+        .map(|start| start.with_timezone(&tz).date_naive())
+}
+
+fn occurrence_of(
+    parent: &Microevent,
+    start: DateTime<Utc>,
+    duration: Option<Duration>,
+    tz: Tz,
+) -> Microevent {
+    Microevent {
+        id: 0,
+        event_id: parent.event_id,
+        user_id: parent.user_id.clone(),
+        name: parent.name.clone(),
+        archive: parent.archive,
+        description: parent.description.clone(),
+        start_time: Some(start),
+        end_time: duration.map(|duration| start + duration),
+        area_id: parent.area_id,
+        track_id: parent.track_id,
+        recurrence_rule: None,
+        recurrence_parent_id: Some(parent.id),
+        occurrence_date: Some(start.with_timezone(&tz).date_naive()),
+        detached: false,
+        cancelled: false,
+        capacity: parent.capacity,
+        attendee_count: 0,
+        seats_remaining: None,
+        approval_status: parent.approval_status,
+        review_note: parent.review_note.clone(),
+        created_at: None,
+        updated_at: None,
     }
 }
@@ -13,3 +13,29 @@ pub mod user_logic;
 pub use user_logic::UserLogic;
 pub mod user_collection_logic;
 pub use user_collection_logic::UserCollectionLogic;
+pub mod trip_logic;
+pub use trip_logic::TripLogic;
+pub mod event_series_logic;
+pub use event_series_logic::EventSeriesLogic;
+pub mod calendar_logic;
+pub use calendar_logic::CalendarLogic;
+pub mod event_import_logic;
+pub use event_import_logic::EventImportLogic;
+pub mod saved_search_logic;
+pub use saved_search_logic::SavedSearchLogic;
+pub mod notification_service;
+pub use notification_service::NotificationService;
+pub mod mail_logic;
+pub use mail_logic::MailLogic;
+pub mod mail_transport;
+pub mod recurrence;
+pub mod webhook_logic;
+pub use webhook_logic::WebhookLogic;
+pub mod change_feed;
+pub use change_feed::{ChangeFeed, Replay};
+pub mod venue_logic;
+pub use venue_logic::VenueLogic;
+pub mod track_logic;
+pub use track_logic::TrackLogic;
+pub mod rsvp_logic;
+pub use rsvp_logic::RsvpLogic;
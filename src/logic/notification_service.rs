@@ -0,0 +1,199 @@
+// ============================================================================
+// src/logic/notification_service.rs - In-app notifications for the logic layer
+// ============================================================================
+use crate::context::NotificationContext;
+use crate::errors::AppError;
+use crate::logic::MailLogic;
+use crate::models::notification_models::{
+    NotificationCategory, NotificationChannel, NotificationListResponse, NotificationPreferences,
+    NotificationQueryParams,
+};
+use std::sync::Arc;
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+///Other logic structs hold an Arc of this and call notify instead of writing
+///notifications themselves, so user preferences are checked in one place.
+pub struct NotificationService {
+    repository: NotificationContext,
+    mail_logic: Arc<MailLogic>,
+}
+
+impl NotificationService {
+    pub fn new(repository: NotificationContext, mail_logic: Arc<MailLogic>) -> Self {
+        Self {
+            repository,
+            mail_logic,
+        }
+    }
+
+    pub async fn get_notifications(
+        &self,
+        user_id: &str,
+        params: &NotificationQueryParams,
+    ) -> Result<NotificationListResponse, AppError> {
+        let limit = params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+        let notifications = self
+            .repository
+            .find_by_user(user_id, params.unread_only, limit)
+            .await?;
+        let unread_count = self.repository.count_unread(user_id).await?;
+
+        Ok(NotificationListResponse {
+            unread_count,
+            notifications,
+        })
+    }
+
+    pub async fn mark_read(&self, id: i64, user_id: &str) -> Result<(), AppError> {
+        let updated = self.repository.mark_read(id, user_id).await?;
+        if !updated {
+            return Err(AppError::NotFound("Notification not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn mark_all_read(&self, user_id: &str) -> Result<u64, AppError> {
+        self.repository.mark_all_read(user_id).await
+    }
+
+    ///Stored settings, or the defaults when there are none (or they can not be read)
+    pub async fn get_preferences(
+        &self,
+        user_id: &str,
+    ) -> Result<NotificationPreferences, AppError> {
+        let Some(raw) = self.repository.find_preferences(user_id).await? else {
+            return Ok(NotificationPreferences::default());
+        };
+
+        match serde_json::from_str(&raw) {
+            Ok(preferences) => Ok(preferences),
+            Err(e) => {
+                tracing::warn!(
+                    "user {} has unreadable notification preferences: {}",
+                    user_id,
+                    e
+                );
+                Ok(NotificationPreferences::default())
+            }
+        }
+    }
+
+    pub async fn update_preferences(
+        &self,
+        user_id: &str,
+        preferences: &NotificationPreferences,
+    ) -> Result<(), AppError> {
+        let raw = serde_json::to_string(preferences)?;
+
+        let updated = self.repository.update_preferences(user_id, &raw).await?;
+        if !updated {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    ///Creates an in-app notification unless the user turned this category off.
+    ///Favorite event changes are also emailed right away, other categories
+    ///reach email through the digest. Returns the new notification id, None
+    ///when no in-app notification was wanted.
+    pub async fn notify(
+        &self,
+        user_id: &str,
+        category: NotificationCategory,
+        title: &str,
+        body: &str,
+        event_id: Option<i64>,
+    ) -> Result<Option<i64>, AppError> {
+        let preferences = self.get_preferences(user_id).await?;
+
+        let email = if category == NotificationCategory::FavoriteEvent
+            && preferences.allows(category, NotificationChannel::Email)
+        {
+            self.mail_logic
+                .event_changed_email(user_id, title, body, event_id)
+                .await?
+        } else {
+            None
+        };
+
+        if !preferences.allows(category, NotificationChannel::InApp) {
+            if let Some(email) = email {
+                self.mail_logic.enqueue(&email).await?;
+            }
+            return Ok(None);
+        }
+
+        let id = self
+            .repository
+            .create(
+                user_id,
+                category.as_str(),
+                title,
+                body,
+                event_id,
+                email.as_ref(),
+            )
+            .await?;
+
+        Ok(Some(id))
+    }
+
+    ///Notifies everyone who favorited the event, except the user who made the change
+    pub async fn notify_event_favorites(
+        &self,
+        event_id: i64,
+        changed_by: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<usize, AppError> {
+        self.notify_favorites(event_id, Some(event_id), changed_by, title, body)
+            .await
+    }
+
+    ///Same as notify_event_favorites, but for an event that is about to be deleted.
+    ///The notifications do not link to it, that would delete them along with the event.
+    pub async fn notify_event_removed(
+        &self,
+        event_id: i64,
+        changed_by: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<usize, AppError> {
+        self.notify_favorites(event_id, None, changed_by, title, body)
+            .await
+    }
+
+    async fn notify_favorites(
+        &self,
+        event_id: i64,
+        link: Option<i64>,
+        changed_by: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<usize, AppError> {
+        let user_ids = self.repository.find_users_favoriting(event_id).await?;
+
+        let mut created = 0;
+        for user_id in user_ids.iter().filter(|id| id.as_str() != changed_by) {
+            let id = self
+                .notify(
+                    user_id,
+                    NotificationCategory::FavoriteEvent,
+                    title,
+                    body,
+                    link,
+                )
+                .await?;
+            if id.is_some() {
+                created += 1;
+            }
+        }
+
+        Ok(created)
+    }
+}
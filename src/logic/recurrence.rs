@@ -0,0 +1,332 @@
+// ============================================================================
+// src/logic/recurrence.rs - The RRULE subset recurring microevents understand
+// ============================================================================
+// FREQ=DAILY or FREQ=WEEKLY with INTERVAL, COUNT, UNTIL and BYDAY (plain
+// day codes, no "1MO" style offsets), e.g. "FREQ=DAILY" or
+// "FREQ=WEEKLY;BYDAY=SA,SU;COUNT=4". Microevents only repeat inside one
+// event, so monthly and yearly rules have nothing to describe.
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+// A year of daily sessions is already more than any gathering runs
+pub const MAX_OCCURRENCES: usize = 366;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+}
+
+///UNTIL as written: a date covers that whole day in the event's time zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Until {
+    Date(NaiveDate),
+    Time(DateTime<Utc>),
+}
+
+#[derive(Debug, Clone)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: i64,
+    pub count: Option<usize>,
+    pub until: Option<Until>,
+    pub by_day: Vec<Weekday>,
+}
+
+impl RecurrenceRule {
+    ///Parses "FREQ=...;..." with or without the "RRULE:" prefix.
+    ///The error is meant for the user, it names the part that is wrong.
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = Vec::new();
+
+        for part in rule.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("{} is not a KEY=VALUE pair", part))?;
+
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        other => {
+                            return Err(format!(
+                                "FREQ={} is not supported, use DAILY or WEEKLY",
+                                other
+                            ));
+                        }
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<i64>()
+                        .ok()
+                        .filter(|n| (1..=MAX_OCCURRENCES as i64).contains(n))
+                        .ok_or_else(|| {
+                            format!(
+                                "INTERVAL={} must be between 1 and {}",
+                                value, MAX_OCCURRENCES
+                            )
+                        })?;
+                }
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|n| (1..=MAX_OCCURRENCES).contains(n))
+                            .ok_or_else(|| {
+                                format!("COUNT={} must be between 1 and {}", value, MAX_OCCURRENCES)
+                            })?,
+                    );
+                }
+                "UNTIL" => {
+                    until = Some(parse_until(value).ok_or_else(|| {
+                        format!(
+                            "UNTIL={} must look like 20260704 or 20260704T235959Z",
+                            value
+                        )
+                    })?);
+                }
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        by_day.push(parse_weekday(day).ok_or_else(|| {
+                            format!("BYDAY {} must be one of MO TU WE TH FR SA SU", day)
+                        })?);
+                    }
+                }
+                other => return Err(format!("{} is not supported", other)),
+            }
+        }
+
+        let frequency = frequency.ok_or_else(|| "FREQ is required".to_string())?;
+        if count.is_some() && until.is_some() {
+            return Err("Use COUNT or UNTIL, not both".to_string());
+        }
+
+        Ok(Self {
+            frequency,
+            interval,
+            count,
+            until,
+            by_day,
+        })
+    }
+
+    ///Start times from first (always the first occurrence) up to, not
+    ///including, window_end, at the same wall clock time in tz as first, so
+    ///a 9:00 session stays at 9:00 across a DST change
+    pub fn occurrences(
+        &self,
+        first: DateTime<Utc>,
+        window_end: DateTime<Utc>,
+        tz: Tz,
+    ) -> Vec<DateTime<Utc>> {
+        let limit = self.count.unwrap_or(MAX_OCCURRENCES).min(MAX_OCCURRENCES);
+        let local_first = first.with_timezone(&tz);
+        let first_day = local_first.date_naive();
+        let time = local_first.time();
+        // weeks count from the Monday of the first occurrence's week
+        let first_monday =
+            first_day - Duration::days(first_day.weekday().num_days_from_monday() as i64);
+
+        let mut starts = vec![first];
+        let mut day = first_day;
+        while starts.len() < limit {
+            day += Duration::days(1);
+            let Some(start) = local_start(tz, day, time) else {
+                break;
+            };
+            let past_until = match self.until {
+                Some(Until::Date(until)) => day > until,
+                Some(Until::Time(until)) => start > until,
+                None => false,
+            };
+            if start >= window_end || past_until {
+                break;
+            }
+
+            if self.matches(day, first_day, first_monday) {
+                starts.push(start);
+            }
+        }
+
+        starts
+    }
+
+    fn matches(&self, day: NaiveDate, first_day: NaiveDate, first_monday: NaiveDate) -> bool {
+        match self.frequency {
+            Frequency::Daily => {
+                (day - first_day).num_days() % self.interval == 0
+                    && (self.by_day.is_empty() || self.by_day.contains(&day.weekday()))
+            }
+            Frequency::Weekly => {
+                let week = (day - first_monday).num_days() / 7;
+                let on_day = if self.by_day.is_empty() {
+                    day.weekday() == first_day.weekday()
+                } else {
+                    self.by_day.contains(&day.weekday())
+                };
+                week % self.interval == 0 && on_day
+            }
+        }
+    }
+}
+
+// A wall clock time that DST skips over moves forward by the gap, as in RFC 5545
+fn local_start(tz: Tz, day: NaiveDate, time: NaiveTime) -> Option<DateTime<Utc>> {
+    let naive = day.and_time(time);
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(naive + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|start| start.with_timezone(&Utc))
+}
+
+fn parse_until(value: &str) -> Option<Until> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d") {
+        return Some(Until::Date(date));
+    }
+    chrono::NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|naive| Until::Time(Utc.from_utc_datetime(&naive)))
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn expand(rule: &str, first: &str, tz: Tz) -> Vec<DateTime<Utc>> {
+        RecurrenceRule::parse(rule)
+            .unwrap()
+            .occurrences(at(first), at("2027-01-01T00:00:00Z"), tz)
+    }
+
+    #[test]
+    fn daily_with_interval_skips_days() {
+        let starts = expand(
+            "FREQ=DAILY;INTERVAL=2;COUNT=3",
+            "2026-07-01T10:00:00Z",
+            Tz::UTC,
+        );
+        assert_eq!(
+            starts,
+            vec![
+                at("2026-07-01T10:00:00Z"),
+                at("2026-07-03T10:00:00Z"),
+                at("2026-07-05T10:00:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn weekly_by_day_repeats_on_each_listed_day() {
+        // 2026-07-04 is a Saturday
+        let starts = expand(
+            "RRULE:FREQ=WEEKLY;BYDAY=SA,SU;COUNT=4",
+            "2026-07-04T18:00:00Z",
+            Tz::UTC,
+        );
+        assert_eq!(
+            starts,
+            vec![
+                at("2026-07-04T18:00:00Z"),
+                at("2026-07-05T18:00:00Z"),
+                at("2026-07-11T18:00:00Z"),
+                at("2026-07-12T18:00:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn count_limits_occurrences() {
+        let starts = expand("FREQ=DAILY;COUNT=2", "2026-07-01T10:00:00Z", Tz::UTC);
+        assert_eq!(starts.len(), 2);
+    }
+
+    #[test]
+    fn until_date_covers_the_whole_day() {
+        let starts = expand("FREQ=DAILY;UNTIL=20260703", "2026-07-01T10:00:00Z", Tz::UTC);
+        assert_eq!(starts.last(), Some(&at("2026-07-03T10:00:00Z")));
+        assert_eq!(starts.len(), 3);
+    }
+
+    #[test]
+    fn until_time_stops_before_a_later_start() {
+        let starts = expand(
+            "FREQ=DAILY;UNTIL=20260703T090000Z",
+            "2026-07-01T10:00:00Z",
+            Tz::UTC,
+        );
+        assert_eq!(starts.last(), Some(&at("2026-07-02T10:00:00Z")));
+        assert_eq!(starts.len(), 2);
+    }
+
+    #[test]
+    fn count_and_until_together_are_rejected() {
+        assert!(RecurrenceRule::parse("FREQ=DAILY;COUNT=2;UNTIL=20260703").is_err());
+    }
+
+    #[test]
+    fn interval_error_names_the_limit() {
+        let error = RecurrenceRule::parse("FREQ=DAILY;INTERVAL=0").unwrap_err();
+        assert_eq!(error, "INTERVAL=0 must be between 1 and 366");
+    }
+
+    #[test]
+    fn local_start_moves_a_skipped_time_forward() {
+        // London skips 01:00-02:00 on 2026-03-29, 01:30 becomes 02:30 BST
+        let day = NaiveDate::from_ymd_opt(2026, 3, 29).unwrap();
+        let time = NaiveTime::from_hms_opt(1, 30, 0).unwrap();
+        assert_eq!(
+            local_start(chrono_tz::Europe::London, day, time),
+            Some(at("2026-03-29T01:30:00Z"))
+        );
+    }
+
+    #[test]
+    fn occurrences_keep_wall_clock_time_across_dst() {
+        let starts = expand(
+            "FREQ=DAILY;COUNT=3",
+            "2026-03-28T09:00:00Z",
+            chrono_tz::Europe::London,
+        );
+        // 9:00 GMT, then 9:00 BST
+        assert_eq!(
+            starts,
+            vec![
+                at("2026-03-28T09:00:00Z"),
+                at("2026-03-29T08:00:00Z"),
+                at("2026-03-30T08:00:00Z"),
+            ]
+        );
+    }
+}
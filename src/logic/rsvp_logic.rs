@@ -0,0 +1,186 @@
+// ============================================================================
+// src/logic/rsvp_logic.rs - Business Logic Layer
+// ============================================================================
+use crate::context::{MicroeventContext, RsvpContext};
+use crate::errors::AppError;
+use crate::logic::{NotificationService, UserCollectionLogic};
+use crate::models::microevents_models::{
+    ApprovalStatus, Attendee, AttendeeList, Microevent, RsvpResult, RsvpStatus,
+};
+use crate::models::notification_models::NotificationCategory;
+use crate::models::user::Claims;
+use std::sync::Arc;
+
+pub struct RsvpLogic {
+    repository: RsvpContext,
+    microevents: MicroeventContext,
+    user_collection_logic: Arc<UserCollectionLogic>,
+    notification_service: Arc<NotificationService>,
+}
+
+impl RsvpLogic {
+    pub fn new(
+        repository: RsvpContext,
+        microevents: MicroeventContext,
+        user_collection_logic: Arc<UserCollectionLogic>,
+        notification_service: Arc<NotificationService>,
+    ) -> Self {
+        Self {
+            repository,
+            microevents,
+            user_collection_logic,
+            notification_service,
+        }
+    }
+
+    ///RSVPing twice is harmless, the user keeps their seat or place in line
+    pub async fn rsvp(&self, microevent_id: i64, user_id: &str) -> Result<RsvpResult, AppError> {
+        let microevent = self.microevents.find_by_id(microevent_id).await?;
+        if microevent.cancelled
+            || microevent.archive
+            || microevent.approval_status != ApprovalStatus::Approved
+        {
+            return Err(AppError::BadRequest(format!(
+                "Microevent {} is no longer taking RSVPs",
+                microevent_id
+            )));
+        }
+
+        self.repository.create(microevent_id, user_id).await?;
+        let (rsvp_id, status) = self
+            .repository
+            .find(microevent_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Microevent not found".to_string()))?;
+
+        let status = RsvpStatus::parse(&status);
+        let waitlist_position = match status {
+            RsvpStatus::Waitlisted => Some(
+                self.repository
+                    .waitlist_position(microevent_id, rsvp_id)
+                    .await?,
+            ),
+            RsvpStatus::Going => None,
+        };
+        let seats_remaining = self
+            .microevents
+            .find_by_id(microevent_id)
+            .await?
+            .seats_remaining;
+
+        Ok(RsvpResult {
+            microevent_id,
+            status,
+            waitlist_position,
+            seats_remaining,
+        })
+    }
+
+    ///Gives up the seat (or place in line), the next on the waitlist gets the seat
+    pub async fn cancel_rsvp(&self, microevent_id: i64, user_id: &str) -> Result<(), AppError> {
+        let promoted = self
+            .repository
+            .delete(microevent_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("No RSVP for this microevent".to_string()))?;
+
+        self.notify_promoted(microevent_id, &promoted).await;
+
+        Ok(())
+    }
+
+    ///Fills seats that opened up without anyone leaving, e.g. a capacity increase
+    pub async fn fill_open_seats(&self, microevent_id: i64) -> Result<(), AppError> {
+        let promoted = self.repository.promote(microevent_id).await?;
+        self.notify_promoted(microevent_id, &promoted).await;
+        Ok(())
+    }
+
+    pub async fn get_attendees(
+        &self,
+        microevent_id: i64,
+        claims: &Claims,
+    ) -> Result<AttendeeList, AppError> {
+        let microevent = self.microevents.find_by_id(microevent_id).await?;
+        self.check_owner(&microevent, claims).await?;
+
+        let mut going = Vec::new();
+        let mut waitlist = Vec::new();
+        for (user_id, user_name, status, rsvp_at) in
+            self.repository.find_attendees(microevent_id).await?
+        {
+            let attendee = Attendee {
+                user_id,
+                user_name,
+                rsvp_at,
+            };
+            match RsvpStatus::parse(&status) {
+                RsvpStatus::Going => going.push(attendee),
+                RsvpStatus::Waitlisted => waitlist.push(attendee),
+            }
+        }
+
+        Ok(AttendeeList {
+            microevent_id,
+            capacity: microevent.capacity,
+            seats_remaining: microevent.seats_remaining,
+            going,
+            waitlist,
+        })
+    }
+
+    ///The seat is already theirs, a failed notification is only logged
+    async fn notify_promoted(&self, microevent_id: i64, user_ids: &[String]) {
+        if user_ids.is_empty() {
+            return;
+        }
+
+        let microevent = match self.microevents.find_by_id(microevent_id).await {
+            Ok(microevent) => microevent,
+            Err(e) => {
+                tracing::warn!("could not load microevent {}: {}", microevent_id, e);
+                return;
+            }
+        };
+
+        let title = format!("You have a seat at {}", microevent.name);
+        let body = "A seat opened up and your RSVP moved off the waitlist.";
+        for user_id in user_ids {
+            if let Err(e) = self
+                .notification_service
+                .notify(
+                    user_id,
+                    NotificationCategory::System,
+                    &title,
+                    body,
+                    Some(microevent.event_id),
+                )
+                .await
+            {
+                tracing::warn!("could not notify user {} of promotion: {}", user_id, e);
+            }
+        }
+    }
+
+    // Owners of a series own every occurrence in it
+    async fn check_owner(&self, microevent: &Microevent, claims: &Claims) -> Result<(), AppError> {
+        let is_admin = claims.role == "admin" || claims.role == "super_admin";
+        if is_admin {
+            return Ok(());
+        }
+
+        let collection = self.user_collection_logic.get(&claims.sub).await?;
+        let is_owner = collection.created_microevents.contains(&microevent.id)
+            || microevent
+                .recurrence_parent_id
+                .is_some_and(|parent_id| collection.created_microevents.contains(&parent_id));
+
+        if !is_owner {
+            return Err(AppError::Unauthorized(
+                "Only the organizer can see who is attending".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+// ============================================================================
+// src/logic/saved_search_logic.rs - Saved searches and new-match alerts
+// ============================================================================
+use crate::context::{EventContext, SavedSearchContext};
+use crate::errors::AppError;
+use crate::logic::{EventLogic, NotificationService};
+use crate::models::database_models::SavedSearchRow;
+use crate::models::dto::EventResponse;
+use crate::models::notification_models::{NotificationCategory, NotificationChannel};
+use crate::models::saved_search_models::{SavedSearch, SavedSearchAlertReport};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+pub struct SavedSearchLogic {
+    repository: SavedSearchContext,
+    notifications: Arc<NotificationService>,
+    events_context: EventContext,
+    event_logic: Arc<EventLogic>,
+}
+
+impl SavedSearchLogic {
+    pub fn new(
+        repository: SavedSearchContext,
+        notifications: Arc<NotificationService>,
+        events_context: EventContext,
+        event_logic: Arc<EventLogic>,
+    ) -> Self {
+        Self {
+            repository,
+            notifications,
+            events_context,
+            event_logic,
+        }
+    }
+
+    pub async fn get_searches(&self, user_id: &str) -> Result<Vec<SavedSearch>, AppError> {
+        let rows = self.repository.find_by_user(user_id).await?;
+        rows.into_iter().map(Self::to_saved_search).collect()
+    }
+
+    pub async fn create_search(&self, user_id: &str, search: SavedSearch) -> Result<i64, AppError> {
+        self.validate_search(&search)?;
+        self.repository.create(user_id, &search).await
+    }
+
+    pub async fn update_search(
+        &self,
+        id: i64,
+        user_id: &str,
+        search: SavedSearch,
+    ) -> Result<(), AppError> {
+        self.validate_search(&search)?;
+
+        let updated = self.repository.update(id, user_id, &search).await?;
+        if !updated {
+            return Err(AppError::NotFound("Saved search not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn delete_search(&self, id: i64, user_id: &str) -> Result<(), AppError> {
+        let deleted = self.repository.delete(id, user_id).await?;
+        if !deleted {
+            return Err(AppError::NotFound("Saved search not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    ///Runs a saved search now, with the same results as GET /event/search
+    pub async fn run_search(&self, id: i64, user_id: &str) -> Result<Vec<EventResponse>, AppError> {
+        let row = self.repository.find_by_id(id).await?;
+        if row.user_id != user_id {
+            return Err(AppError::NotFound("Saved search not found".to_string()));
+        }
+
+        let search = Self::to_saved_search(row)?;
+        self.event_logic.search_events(&search.query).await
+    }
+
+    ///One pass of the background job. Every search with alerts on is checked against
+    ///the events created or updated since its last pass.
+    pub async fn run_alerts(&self) -> Result<SavedSearchAlertReport, AppError> {
+        let mut report = SavedSearchAlertReport::default();
+
+        for row in self.repository.find_alerting().await? {
+            let search_id = row.id;
+
+            let (created, checked_at, checked_ids) = match self.check_search(row).await {
+                Ok(result) => result,
+                Err(e) => {
+                    // leave last_checked_at alone so the next pass retries
+                    tracing::warn!("saved search {} alert check failed: {}", search_id, e);
+                    continue;
+                }
+            };
+            report.notifications_created += created;
+
+            self.repository
+                .set_last_checked(search_id, &checked_at, &checked_ids)
+                .await?;
+            report.searches_checked += 1;
+        }
+
+        Ok(report)
+    }
+
+    ///Notifies about the changed events matching the search. Returns the count and the
+    ///new cursor: the newest modified_at seen and the events stamped with it, which the
+    ///inclusive lookup would otherwise report again next pass.
+    async fn check_search(
+        &self,
+        row: SavedSearchRow,
+    ) -> Result<(usize, String, Vec<i64>), AppError> {
+        let handled: HashSet<i64> = serde_json::from_str(&row.checked_event_ids)?;
+        let modified = self
+            .events_context
+            .find_modified_since(&row.last_checked_at)
+            .await?;
+
+        let checked_at = modified
+            .iter()
+            .map(|(_, modified_at)| modified_at.clone())
+            .max()
+            .unwrap_or_else(|| row.last_checked_at.clone());
+        let checked_ids: Vec<i64> = modified
+            .iter()
+            .filter(|(_, modified_at)| *modified_at == checked_at)
+            .map(|(id, _)| *id)
+            .collect();
+
+        let changed: HashSet<i64> = modified
+            .into_iter()
+            .filter(|(id, modified_at)| {
+                !(*modified_at == row.last_checked_at && handled.contains(id))
+            })
+            .map(|(id, _)| id)
+            .collect();
+        if changed.is_empty() {
+            return Ok((0, checked_at, checked_ids));
+        }
+
+        // no point running the search for someone who turned these off
+        let preferences = self.notifications.get_preferences(&row.user_id).await?;
+        if !preferences.allows(
+            NotificationCategory::SavedSearch,
+            NotificationChannel::InApp,
+        ) {
+            return Ok((0, checked_at, checked_ids));
+        }
+
+        let user_id = row.user_id.clone();
+        let search = Self::to_saved_search(row)?;
+        let matches = self.event_logic.search_events(&search.query).await?;
+
+        let mut created = 0;
+        for event in matches {
+            let Some(event_id) = event.id.filter(|id| changed.contains(id)) else {
+                continue;
+            };
+
+            let id = self
+                .notifications
+                .notify(
+                    &user_id,
+                    NotificationCategory::SavedSearch,
+                    &format!("New match for \"{}\"", search.name),
+                    &format!("{} matches your saved search.", event.name),
+                    Some(event_id),
+                )
+                .await?;
+            if id.is_some() {
+                created += 1;
+            }
+        }
+
+        Ok((created, checked_at, checked_ids))
+    }
+
+    fn validate_search(&self, search: &SavedSearch) -> Result<(), AppError> {
+        if search.name.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Saved search name cannot be empty".to_string(),
+            ));
+        }
+        if let Some(radius_miles) = search.query.radius_miles
+            && (radius_miles <= 0.0 || radius_miles > 500.0)
+        {
+            return Err(AppError::ValidationError(
+                "Radius must be between 0 and 500 miles".to_string(),
+            ));
+        }
+        search.query.bounding_box()?;
+
+        Ok(())
+    }
+
+    fn to_saved_search(row: SavedSearchRow) -> Result<SavedSearch, AppError> {
+        Ok(SavedSearch {
+            id: Some(row.id),
+            name: row.name,
+            query: serde_json::from_str(&row.query)?,
+            alerts_enabled: row.alerts_enabled,
+            created_at: Some(row.created_at),
+        })
+    }
+}
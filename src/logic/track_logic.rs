@@ -0,0 +1,160 @@
+// ============================================================================
+// src/logic/track_logic.rs - Per-event microevent tracks and categories
+// ============================================================================
+use crate::context::{EventContext, MicroeventTrackContext};
+use crate::errors::{AppError, FieldError};
+use crate::logic::UserCollectionLogic;
+use crate::models::microevents_models::{MicroeventTrack, MicroeventTrackRequest};
+use crate::models::user::Claims;
+use std::sync::Arc;
+
+pub struct TrackLogic {
+    repository: MicroeventTrackContext,
+    events: EventContext,
+    user_collection_logic: Arc<UserCollectionLogic>,
+}
+
+impl TrackLogic {
+    pub fn new(
+        repository: MicroeventTrackContext,
+        events: EventContext,
+        user_collection_logic: Arc<UserCollectionLogic>,
+    ) -> Self {
+        Self {
+            repository,
+            events,
+            user_collection_logic,
+        }
+    }
+
+    pub async fn get_tracks(&self, event_id: i64) -> Result<Vec<MicroeventTrack>, AppError> {
+        self.events.find_by_id(event_id).await?;
+        self.repository.find_by_event(event_id).await
+    }
+
+    pub async fn create_track(
+        &self,
+        event_id: i64,
+        request: MicroeventTrackRequest,
+        claims: &Claims,
+    ) -> Result<MicroeventTrack, AppError> {
+        self.check_event_owner(event_id, claims).await?;
+        validate_request(&request)?;
+        self.check_name_free(event_id, None, &request.name).await?;
+
+        let id = self.repository.create(event_id, &request).await?;
+        self.repository.find_by_id(id).await
+    }
+
+    pub async fn update_track(
+        &self,
+        event_id: i64,
+        track_id: i64,
+        request: MicroeventTrackRequest,
+        claims: &Claims,
+    ) -> Result<MicroeventTrack, AppError> {
+        self.check_event_owner(event_id, claims).await?;
+        self.find_in_event(event_id, track_id).await?;
+        validate_request(&request)?;
+        self.check_name_free(event_id, Some(track_id), &request.name)
+            .await?;
+
+        let updated = self.repository.update(track_id, &request).await?;
+        if !updated {
+            return Err(AppError::NotFound("Track not found".to_string()));
+        }
+
+        self.repository.find_by_id(track_id).await
+    }
+
+    pub async fn delete_track(
+        &self,
+        event_id: i64,
+        track_id: i64,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        self.check_event_owner(event_id, claims).await?;
+        self.find_in_event(event_id, track_id).await?;
+
+        let deleted = self.repository.delete(track_id).await?;
+        if !deleted {
+            return Err(AppError::NotFound("Track not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn find_in_event(
+        &self,
+        event_id: i64,
+        track_id: i64,
+    ) -> Result<MicroeventTrack, AppError> {
+        let track = self.repository.find_by_id(track_id).await?;
+        if track.event_id != event_id {
+            return Err(AppError::NotFound("Track not found".to_string()));
+        }
+        Ok(track)
+    }
+
+    // Two "Music" tracks in one event would make the filter useless
+    async fn check_name_free(
+        &self,
+        event_id: i64,
+        track_id: Option<i64>,
+        name: &str,
+    ) -> Result<(), AppError> {
+        let taken = self
+            .repository
+            .find_by_event(event_id)
+            .await?
+            .iter()
+            .any(|t| Some(t.id) != track_id && t.name.eq_ignore_ascii_case(name.trim()));
+        if taken {
+            return Err(AppError::Conflict(format!(
+                "This event already has a track named {}",
+                name.trim()
+            )));
+        }
+        Ok(())
+    }
+
+    async fn check_event_owner(&self, event_id: i64, claims: &Claims) -> Result<(), AppError> {
+        self.events.find_by_id(event_id).await?;
+
+        // Check if user is admin or superadmin (bypass ownership check)
+        let is_admin = claims.role == "admin" || claims.role == "super_admin";
+        if is_admin {
+            return Ok(());
+        }
+
+        let collection = self.user_collection_logic.get(&claims.sub).await?;
+        if !collection.created_events.contains(&event_id) {
+            return Err(AppError::Unauthorized(
+                "You do not have permission to change this event's tracks".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_request(request: &MicroeventTrackRequest) -> Result<(), AppError> {
+    let mut errors = Vec::new();
+
+    if request.name.trim().is_empty() {
+        errors.push(FieldError::new("name", "Track name cannot be empty"));
+    }
+
+    if let Some(color) = &request.color {
+        let hex = color.strip_prefix('#').unwrap_or_default();
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            errors.push(FieldError::new("color", "Use a hex color like #1e90ff"));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::InvalidFields(errors))
+    }
+}
@@ -0,0 +1,138 @@
+// ============================================================================
+// src/logic/trip_logic.rs - Multi-stop trip planning over saved events
+// ============================================================================
+use crate::errors::AppError;
+use crate::logic::UserCollectionLogic;
+use crate::models::dto::EventResponse;
+use crate::models::trip_models::{TripItinerary, TripLeg, TripStop};
+use std::sync::Arc;
+
+const EARTH_RADIUS_MILES: f64 = 3958.8;
+
+pub struct TripLogic {
+    user_collection_logic: Arc<UserCollectionLogic>,
+}
+
+impl TripLogic {
+    pub fn new(user_collection_logic: Arc<UserCollectionLogic>) -> Self {
+        Self {
+            user_collection_logic,
+        }
+    }
+
+    pub async fn plan(&self, user_id: &String) -> Result<TripItinerary, AppError> {
+        let events = self.user_collection_logic.get_saved_events(user_id).await?;
+
+        let (mut scheduled, unscheduled): (Vec<EventResponse>, Vec<EventResponse>) = events
+            .into_iter()
+            .partition(|event| event.date_info.start_date.is_some());
+
+        scheduled.sort_by_key(|event| event.date_info.start_date);
+
+        let legs: Vec<TripLeg> = scheduled
+            .windows(2)
+            .map(|pair| Self::build_leg(&pair[0], &pair[1]))
+            .collect();
+
+        let total_miles = legs.iter().map(|leg| leg.distance_miles).sum();
+        let total_boondocking_nights = legs.iter().map(|leg| leg.boondocking_nights).sum();
+
+        let stops = scheduled
+            .into_iter()
+            .enumerate()
+            .map(|(i, event)| TripStop {
+                sequence: i + 1,
+                event,
+            })
+            .collect();
+
+        Ok(TripItinerary {
+            stops,
+            legs,
+            unscheduled,
+            total_miles,
+            total_boondocking_nights,
+        })
+    }
+
+    pub fn to_gpx(&self, itinerary: &TripItinerary) -> String {
+        let mut gpx = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <gpx version=\"1.1\" creator=\"rust_nomad_events_api\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n",
+        );
+
+        for stop in &itinerary.stops {
+            let location = &stop.event.location_info;
+            gpx.push_str(&format!(
+                "  <wpt lat=\"{}\" lon=\"{}\">\n    <name>{}</name>\n    <desc>{}</desc>\n  </wpt>\n",
+                location.latitude,
+                location.longitude,
+                xml_escape(&stop.event.name),
+                xml_escape(&location.address),
+            ));
+        }
+
+        gpx.push_str("  <rte>\n    <name>Saved events trip</name>\n");
+        for stop in &itinerary.stops {
+            let location = &stop.event.location_info;
+            gpx.push_str(&format!(
+                "    <rtept lat=\"{}\" lon=\"{}\">\n      <name>{}</name>\n    </rtept>\n",
+                location.latitude,
+                location.longitude,
+                xml_escape(&stop.event.name),
+            ));
+        }
+        gpx.push_str("  </rte>\n</gpx>\n");
+
+        gpx
+    }
+
+    fn build_leg(from: &EventResponse, to: &EventResponse) -> TripLeg {
+        let distance_miles = great_circle_miles(
+            from.location_info.latitude,
+            from.location_info.longitude,
+            to.location_info.latitude,
+            to.location_info.longitude,
+        );
+
+        // Single day events (or ones missing an end) are left the day they start
+        let leave = from.date_info.end_date.or(from.date_info.start_date);
+        let gap_days = match (leave, to.date_info.start_date) {
+            (Some(leave), Some(arrive)) => (arrive.date_naive() - leave.date_naive()).num_days(),
+            _ => 0,
+        };
+
+        let covered_nights =
+            from.date_info.late_departure_nights() + to.date_info.early_arrival_nights();
+        let boondocking_nights = (gap_days - covered_nights).max(0);
+
+        TripLeg {
+            from_event_id: from.id,
+            to_event_id: to.id,
+            distance_miles,
+            gap_days,
+            overlapping: gap_days < 0,
+            boondocking_nights,
+        }
+    }
+}
+
+///Haversine distance between two coordinates
+pub fn great_circle_miles(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lon = (lon2 - lon1).to_radians();
+
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_MILES * a.sqrt().asin()
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -3,18 +3,23 @@
 // ============================================================================
 use crate::context::EventContext;
 use crate::context::MicroeventContext;
+use crate::context::MicroeventTrackContext;
 use crate::context::UserCollectionContext;
 use crate::errors::AppError;
 //use crate::repositories::EventRepository;
 use crate::models::database_models::UserEventDataRow;
 use crate::models::dto::EventResponse;
 use crate::models::dto::UserCollection;
-use crate::models::microevents_models::Microevent;
+use crate::models::microevents_models::{
+    ApprovalStatus, Microevent, MicroeventFilterParams, PersonalSchedule, ScheduleConflict,
+    ScheduleItem, ToggleScope,
+};
 use crate::models::user::Claims;
 pub struct UserCollectionLogic {
     repository: UserCollectionContext,
     events_context: EventContext,
     microevents_context: MicroeventContext,
+    tracks_context: MicroeventTrackContext,
 }
 
 impl UserCollectionLogic {
@@ -22,11 +27,13 @@ impl UserCollectionLogic {
         repository: UserCollectionContext,
         events_context: EventContext,
         microevents_context: MicroeventContext,
+        tracks_context: MicroeventTrackContext,
     ) -> Self {
         Self {
             repository,
             events_context,
             microevents_context,
+            tracks_context,
         }
     }
 
@@ -56,6 +63,7 @@ impl UserCollectionLogic {
             saved_microevents: input.saved_microevents.unwrap_or_default(),
             created_events: input.created_events.unwrap_or_default(),
             created_microevents: input.created_microevents.unwrap_or_default(),
+            favorite_series: input.favorite_series.unwrap_or_default(),
         };
 
         self.repository.update(&row).await?;
@@ -106,6 +114,8 @@ impl UserCollectionLogic {
             saved_microevents: input.saved_microevents.unwrap_or_default(),
             created_events: ogrow.created_events,
             created_microevents: ogrow.created_microevents,
+            // Older clients do not send series favorites, keep what is stored
+            favorite_series: input.favorite_series.unwrap_or(ogrow.favorite_series),
         };
 
         self.repository.update(&row).await?;
@@ -141,18 +151,34 @@ impl UserCollectionLogic {
         &self,
         id: i64,
         user_id: &String,
+        scope: ToggleScope,
     ) -> Result<Vec<i64>, AppError> {
         let mut data = self.repository.get(user_id.to_string()).await?;
 
-        if let Some(pos) = data.favorite_microevents.iter().position(|x| x == &id) {
-            data.favorite_microevents.remove(pos);
+        let ids = self.toggle_ids(id, scope).await?;
+        toggle_all(&mut data.favorite_microevents, &ids);
+
+        self.repository.update(&data).await?;
+
+        Ok(data.favorite_microevents)
+    }
+
+    pub async fn series_favorite_toggle(
+        &self,
+        id: i64,
+        user_id: &String,
+    ) -> Result<Vec<i64>, AppError> {
+        let mut data = self.repository.get(user_id.to_string()).await?;
+
+        if let Some(pos) = data.favorite_series.iter().position(|x| *x == id) {
+            data.favorite_series.remove(pos);
         } else {
-            data.favorite_microevents.push(id);
+            data.favorite_series.push(id);
         }
 
         self.repository.update(&data).await?;
 
-        Ok(data.favorite_microevents)
+        Ok(data.favorite_series)
     }
 
     ///save toggle
@@ -174,20 +200,151 @@ impl UserCollectionLogic {
         &self,
         id: i64,
         user_id: &String,
+        scope: ToggleScope,
     ) -> Result<Vec<i64>, AppError> {
         let mut data = self.repository.get(user_id.to_string()).await?;
 
-        if let Some(pos) = data.saved_microevents.iter().position(|x| x == &id) {
-            data.saved_microevents.remove(pos);
-        } else {
-            data.saved_microevents.push(id);
-        }
+        let ids = self.toggle_ids(id, scope).await?;
+        toggle_all(&mut data.saved_microevents, &ids);
 
         self.repository.update(&data).await?;
 
         Ok(data.saved_microevents)
     }
 
+    ///Just the microevent, or every occurrence of the series it belongs to
+    async fn toggle_ids(&self, id: i64, scope: ToggleScope) -> Result<Vec<i64>, AppError> {
+        if scope == ToggleScope::Occurrence {
+            return Ok(vec![id]);
+        }
+
+        let microevent = self.microevents_context.find_by_id(id).await?;
+        let parent_id = microevent.recurrence_parent_id.unwrap_or(id);
+
+        let mut ids = vec![parent_id];
+        ids.extend(
+            self.microevents_context
+                .find_occurrences(parent_id)
+                .await?
+                .iter()
+                .map(|m| m.id),
+        );
+        Ok(ids)
+    }
+
+    ///Conflicts between one saved microevent and the rest of the user's saved microevents
+    ///on the same event. Empty when the microevent is not (or no longer) saved.
+    pub async fn microevent_save_conflicts(
+        &self,
+        id: i64,
+        user_id: &String,
+    ) -> Result<Vec<ScheduleConflict>, AppError> {
+        let data = self.repository.get(user_id.to_string()).await?;
+        if !data.saved_microevents.contains(&id) {
+            return Ok(vec![]);
+        }
+
+        let target = self.microevents_context.find_by_id(id).await?;
+        let saved: Vec<Microevent> = self
+            .microevents_context
+            .get_by_id_list(data.saved_microevents)
+            .await?
+            .into_iter()
+            .filter(|m| m.event_id == target.event_id)
+            .collect();
+
+        let conflicts = find_conflicts(&saved)
+            .into_iter()
+            .filter(|c| c.first_id == id || c.second_id == id)
+            .collect();
+
+        Ok(conflicts)
+    }
+
+    ///The user's schedule for one event with overlapping microevents flagged.
+    ///Filtered like GET /event/{id}/microevent, so a saved microevent that was
+    ///archived, cancelled or is still waiting for approval drops out.
+    pub async fn get_personal_schedule(
+        &self,
+        event_id: i64,
+        claims: &Claims,
+        filter: &MicroeventFilterParams,
+    ) -> Result<PersonalSchedule, AppError> {
+        let track_filter = filter.track_filter()?;
+        let data = self.repository.get(claims.sub.clone()).await?;
+
+        let manages = claims.role == "admin"
+            || claims.role == "super_admin"
+            || data.created_events.contains(&event_id);
+        let behind = |m: &Microevent| {
+            manages
+                || data.created_microevents.contains(&m.id)
+                || m.recurrence_parent_id
+                    .is_some_and(|parent_id| data.created_microevents.contains(&parent_id))
+        };
+        let show = |m: &Microevent| {
+            (!m.cancelled || filter.include_cancelled)
+                && (!m.archive || (filter.include_archived && behind(m)))
+                && (m.approval_status == ApprovalStatus::Approved || behind(m))
+        };
+
+        let mut ids = data.saved_microevents.clone();
+        for id in &data.favorite_microevents {
+            if !ids.contains(id) {
+                ids.push(*id);
+            }
+        }
+
+        let mut microevents: Vec<Microevent> = self
+            .microevents_context
+            .get_by_id_list(ids)
+            .await?
+            .into_iter()
+            .filter(|m| m.event_id == event_id)
+            .filter(|m| {
+                track_filter
+                    .as_ref()
+                    .is_none_or(|t| t.contains(&m.track_id))
+            })
+            .filter(show)
+            .collect();
+        microevents.sort_by_key(|m| m.start_time);
+
+        let conflicts = find_conflicts(&microevents);
+
+        let items = microevents
+            .into_iter()
+            .map(|microevent| {
+                let conflicts_with = conflicts
+                    .iter()
+                    .filter_map(|c| {
+                        if c.first_id == microevent.id {
+                            Some(c.second_id)
+                        } else if c.second_id == microevent.id {
+                            Some(c.first_id)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+
+                ScheduleItem {
+                    saved: data.saved_microevents.contains(&microevent.id),
+                    favorite: data.favorite_microevents.contains(&microevent.id),
+                    conflicts_with,
+                    microevent,
+                }
+            })
+            .collect();
+
+        Ok(PersonalSchedule {
+            event_id,
+            tracks: self.tracks_context.find_by_event(event_id).await?,
+            items,
+            conflicts,
+        })
+    }
+
     ///Add ownership of events and microevents
     pub async fn event_ownership(&self, id: i64, user_id: &String) -> Result<Vec<i64>, AppError> {
         let mut data = self.repository.get(user_id.to_string()).await?;
@@ -196,6 +353,18 @@ impl UserCollectionLogic {
         Ok(data.created_events)
     }
 
+    ///Bulk version of microevent_ownership, for the occurrences of a series
+    pub async fn microevents_ownership(
+        &self,
+        ids: &[i64],
+        user_id: &String,
+    ) -> Result<Vec<i64>, AppError> {
+        let mut data = self.repository.get(user_id.to_string()).await?;
+        data.created_microevents.extend_from_slice(ids);
+        self.repository.update(&data).await?;
+        Ok(data.created_microevents)
+    }
+
     pub async fn microevent_ownership(
         &self,
         id: i64,
@@ -248,10 +417,7 @@ impl UserCollectionLogic {
             .get_by_id_list(preoutput.created_events)
             .await?;
 
-        let output: Vec<EventResponse> = rows
-            .into_iter()
-            .filter_map(|row| EventResponse::from_row(row).ok())
-            .collect();
+        let output = EventResponse::from_rows(rows);
 
         //let output = self
         //.events_logic
@@ -291,10 +457,7 @@ impl UserCollectionLogic {
             .get_by_id_list(preoutput.favorite_events)
             .await?;
 
-        let output: Vec<EventResponse> = rows
-            .into_iter()
-            .filter_map(|row| EventResponse::from_row(row).ok())
-            .collect();
+        let output = EventResponse::from_rows(rows);
         Ok(output)
     }
 
@@ -320,10 +483,7 @@ impl UserCollectionLogic {
             .get_by_id_list(preoutput.saved_events)
             .await?;
 
-        let output: Vec<EventResponse> = rows
-            .into_iter()
-            .filter_map(|row| EventResponse::from_row(row).ok())
-            .collect();
+        let output = EventResponse::from_rows(rows);
 
         Ok(output)
     }
@@ -342,3 +502,48 @@ impl UserCollectionLogic {
         Ok(output)
     }
 }
+
+///Removes all of ids when every one is in the list, otherwise adds the missing ones
+fn toggle_all(list: &mut Vec<i64>, ids: &[i64]) {
+    if ids.iter().all(|id| list.contains(id)) {
+        list.retain(|id| !ids.contains(id));
+    } else {
+        for id in ids {
+            if !list.contains(id) {
+                list.push(*id);
+            }
+        }
+    }
+}
+
+///Every pair of microevents whose time windows overlap.
+///Microevents missing a start or end time can't conflict with anything.
+fn find_conflicts(microevents: &[Microevent]) -> Vec<ScheduleConflict> {
+    let mut conflicts = Vec::new();
+    // a cancelled session doesn't get in the way of anything
+    let microevents: Vec<&Microevent> = microevents.iter().filter(|m| !m.cancelled).collect();
+
+    for (i, a) in microevents.iter().enumerate() {
+        let (Some(a_start), Some(a_end)) = (a.start_time, a.end_time) else {
+            continue;
+        };
+
+        for b in &microevents[i + 1..] {
+            let (Some(b_start), Some(b_end)) = (b.start_time, b.end_time) else {
+                continue;
+            };
+
+            // Back to back sessions (one ends as the next starts) are fine
+            if a_start < b_end && b_start < a_end {
+                conflicts.push(ScheduleConflict {
+                    first_id: a.id,
+                    second_id: b.id,
+                    overlap_start: a_start.max(b_start),
+                    overlap_end: a_end.min(b_end),
+                });
+            }
+        }
+    }
+
+    conflicts
+}
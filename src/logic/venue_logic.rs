@@ -0,0 +1,355 @@
+// ============================================================================
+// src/logic/venue_logic.rs - Venue areas, the venue map and grid schedules
+// ============================================================================
+use crate::context::{EventContext, MicroeventContext, VenueAreaContext};
+use crate::errors::{AppError, FieldError};
+use crate::logic::UserCollectionLogic;
+use crate::models::database_models::VenueAreaRow;
+use crate::models::geojson::{Feature, FeatureCollection, Geometry, MicroeventFeatureProperties};
+use crate::models::microevents_models::ApprovalStatus;
+use crate::models::user::Claims;
+use crate::models::venue_models::{
+    AreaFeatureProperties, AreaKind, ScheduleGrid, ScheduleGridColumn, ScheduleGridDay, VenueArea,
+    VenueAreaRequest,
+};
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+// An outline with more corners than this is a traced map, not a tent
+const MAX_POLYGON_POINTS: usize = 500;
+
+pub struct VenueLogic {
+    repository: VenueAreaContext,
+    events: EventContext,
+    microevents: MicroeventContext,
+    user_collection_logic: Arc<UserCollectionLogic>,
+}
+
+impl VenueLogic {
+    pub fn new(
+        repository: VenueAreaContext,
+        events: EventContext,
+        microevents: MicroeventContext,
+        user_collection_logic: Arc<UserCollectionLogic>,
+    ) -> Self {
+        Self {
+            repository,
+            events,
+            microevents,
+            user_collection_logic,
+        }
+    }
+
+    pub async fn get_areas(&self, event_id: i64) -> Result<Vec<VenueArea>, AppError> {
+        self.events.find_by_id(event_id).await?;
+
+        let rows = self.repository.find_by_event(event_id).await?;
+        Ok(rows.into_iter().map(to_area).collect())
+    }
+
+    pub async fn create_area(
+        &self,
+        event_id: i64,
+        request: VenueAreaRequest,
+        claims: &Claims,
+    ) -> Result<VenueArea, AppError> {
+        self.check_event_owner(event_id, claims).await?;
+        let polygon = validate_request(&request)?;
+
+        let id = self
+            .repository
+            .create(event_id, &request, polygon.as_deref())
+            .await?;
+
+        let row = self.repository.find_by_id(id).await?;
+        Ok(to_area(row))
+    }
+
+    pub async fn update_area(
+        &self,
+        event_id: i64,
+        area_id: i64,
+        request: VenueAreaRequest,
+        claims: &Claims,
+    ) -> Result<VenueArea, AppError> {
+        self.check_event_owner(event_id, claims).await?;
+        self.find_in_event(event_id, area_id).await?;
+        let polygon = validate_request(&request)?;
+
+        let updated = self
+            .repository
+            .update(area_id, &request, polygon.as_deref())
+            .await?;
+        if !updated {
+            return Err(AppError::NotFound("Area not found".to_string()));
+        }
+
+        let row = self.repository.find_by_id(area_id).await?;
+        Ok(to_area(row))
+    }
+
+    pub async fn delete_area(
+        &self,
+        event_id: i64,
+        area_id: i64,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        self.check_event_owner(event_id, claims).await?;
+        self.find_in_event(event_id, area_id).await?;
+
+        let deleted = self.repository.delete(area_id).await?;
+        if !deleted {
+            return Err(AppError::NotFound("Area not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    ///GeoJSON for the venue map, areas without coordinates are left out
+    pub async fn get_map(
+        &self,
+        event_id: i64,
+    ) -> Result<FeatureCollection<AreaFeatureProperties, Geometry>, AppError> {
+        let features = self
+            .get_areas(event_id)
+            .await?
+            .into_iter()
+            .filter_map(|area| {
+                let geometry = area_geometry(&area)?;
+
+                Some(Feature::new(
+                    area.id,
+                    geometry,
+                    AreaFeatureProperties {
+                        name: area.name,
+                        kind: area.kind,
+                        description: area.description,
+                    },
+                ))
+            })
+            .collect();
+
+        Ok(FeatureCollection::new(features))
+    }
+
+    ///GeoJSON for the microevents, each drawn at its area's point or outline.
+    ///Microevents without a mapped area are left out, like archived,
+    ///unapproved and cancelled ones.
+    pub async fn get_microevent_map(
+        &self,
+        event_id: i64,
+    ) -> Result<FeatureCollection<MicroeventFeatureProperties, Geometry>, AppError> {
+        let areas = self.get_areas(event_id).await?;
+        let microevents = self.microevents.find_by_event(event_id).await?;
+
+        let features = microevents
+            .into_iter()
+            .filter(|m| !m.archive && !m.cancelled && m.approval_status == ApprovalStatus::Approved)
+            .filter_map(|microevent| {
+                let area = areas
+                    .iter()
+                    .find(|area| Some(area.id) == microevent.area_id)?;
+                let geometry = area_geometry(area)?;
+
+                Some(Feature::new(
+                    microevent.id,
+                    geometry,
+                    MicroeventFeatureProperties {
+                        name: microevent.name,
+                        area_id: area.id,
+                        area_name: area.name.clone(),
+                        start_time: microevent.start_time,
+                        end_time: microevent.end_time,
+                    },
+                ))
+            })
+            .collect();
+
+        Ok(FeatureCollection::new(features))
+    }
+
+    ///The event's microevents by day (in the event's time zone, from start_time)
+    ///and area, for grid-style schedules. Archived, unapproved and cancelled
+    ///microevents are left out.
+    pub async fn get_grid(&self, event_id: i64) -> Result<ScheduleGrid, AppError> {
+        let areas = self.get_areas(event_id).await?;
+        let microevents = self.microevents.find_by_event(event_id).await?;
+        let tz = self
+            .events
+            .find_stored(event_id)
+            .await?
+            .location_info
+            .tz()
+            .unwrap_or(Tz::UTC);
+
+        let mut unscheduled = Vec::new();
+        let mut by_day: BTreeMap<NaiveDate, BTreeMap<Option<i64>, Vec<_>>> = BTreeMap::new();
+        for microevent in microevents
+            .into_iter()
+            .filter(|m| !m.archive && !m.cancelled && m.approval_status == ApprovalStatus::Approved)
+        {
+            let Some(start) = microevent.start_time else {
+                unscheduled.push(microevent);
+                continue;
+            };
+            // an area_id from another event (or a deleted area) counts as unassigned
+            let area_id = microevent
+                .area_id
+                .filter(|id| areas.iter().any(|area| area.id == *id));
+
+            by_day
+                .entry(start.with_timezone(&tz).date_naive())
+                .or_default()
+                .entry(area_id)
+                .or_default()
+                .push(microevent);
+        }
+
+        let days = by_day
+            .into_iter()
+            .map(|(date, mut cells)| {
+                let mut columns: Vec<ScheduleGridColumn> = areas
+                    .iter()
+                    .map(|area| ScheduleGridColumn {
+                        area_id: Some(area.id),
+                        microevents: cells.remove(&Some(area.id)).unwrap_or_default(),
+                    })
+                    .collect();
+                if let Some(unassigned) = cells.remove(&None) {
+                    columns.push(ScheduleGridColumn {
+                        area_id: None,
+                        microevents: unassigned,
+                    });
+                }
+
+                ScheduleGridDay { date, columns }
+            })
+            .collect();
+
+        Ok(ScheduleGrid {
+            event_id,
+            areas,
+            days,
+            unscheduled,
+        })
+    }
+
+    async fn find_in_event(&self, event_id: i64, area_id: i64) -> Result<VenueAreaRow, AppError> {
+        let row = self.repository.find_by_id(area_id).await?;
+        if row.event_id != event_id {
+            return Err(AppError::NotFound("Area not found".to_string()));
+        }
+        Ok(row)
+    }
+
+    async fn check_event_owner(&self, event_id: i64, claims: &Claims) -> Result<(), AppError> {
+        self.events.find_by_id(event_id).await?;
+
+        // Check if user is admin or superadmin (bypass ownership check)
+        let is_admin = claims.role == "admin" || claims.role == "super_admin";
+        if is_admin {
+            return Ok(());
+        }
+
+        let collection = self.user_collection_logic.get(&claims.sub).await?;
+        if !collection.created_events.contains(&event_id) {
+            return Err(AppError::Unauthorized(
+                "You do not have permission to change this event's areas".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+///The outline says more than the marker when there are both
+fn area_geometry(area: &VenueArea) -> Option<Geometry> {
+    match (&area.polygon, area.longitude, area.latitude) {
+        (Some(ring), _, _) => Some(Geometry::Polygon {
+            coordinates: vec![ring.clone()],
+        }),
+        (None, Some(longitude), Some(latitude)) => Some(Geometry::Point {
+            coordinates: [longitude, latitude],
+        }),
+        _ => None,
+    }
+}
+
+///Checks the request and returns the outline as stored, closed and serialized
+fn validate_request(request: &VenueAreaRequest) -> Result<Option<String>, AppError> {
+    let mut errors = Vec::new();
+
+    if request.name.trim().is_empty() {
+        errors.push(FieldError::new("name", "Area name cannot be empty"));
+    }
+
+    match (request.latitude, request.longitude) {
+        (Some(latitude), Some(longitude)) => {
+            if !(-90.0..=90.0).contains(&latitude) {
+                errors.push(FieldError::new("latitude", "Must be between -90 and 90"));
+            }
+            if !(-180.0..=180.0).contains(&longitude) {
+                errors.push(FieldError::new("longitude", "Must be between -180 and 180"));
+            }
+        }
+        (None, None) => {}
+        (None, Some(_)) => errors.push(FieldError::new("latitude", "Required with longitude")),
+        (Some(_), None) => errors.push(FieldError::new("longitude", "Required with latitude")),
+    }
+
+    let mut ring = request.polygon.clone().unwrap_or_default();
+    if request.polygon.is_some() {
+        if ring.first() != ring.last() || ring.len() == 1 {
+            // GeoJSON rings end where they start
+            ring.push(ring[0]);
+        }
+
+        if ring.len() < 4 {
+            errors.push(FieldError::new(
+                "polygon",
+                "An outline needs at least 3 corners",
+            ));
+        } else if ring.len() > MAX_POLYGON_POINTS {
+            errors.push(FieldError::new(
+                "polygon",
+                format!("At most {} corners", MAX_POLYGON_POINTS),
+            ));
+        }
+        if ring.iter().any(|[longitude, latitude]| {
+            !(-180.0..=180.0).contains(longitude) || !(-90.0..=90.0).contains(latitude)
+        }) {
+            errors.push(FieldError::new(
+                "polygon",
+                "Corners are [longitude, latitude] pairs within -180..180 and -90..90",
+            ));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(AppError::InvalidFields(errors));
+    }
+
+    match request.polygon {
+        Some(_) => Ok(Some(serde_json::to_string(&ring)?)),
+        None => Ok(None),
+    }
+}
+
+fn to_area(row: VenueAreaRow) -> VenueArea {
+    VenueArea {
+        id: row.id,
+        event_id: row.event_id,
+        name: row.name,
+        kind: serde_json::from_value(serde_json::Value::from(row.kind)).unwrap_or(AreaKind::Other),
+        description: row.description,
+        latitude: row.latitude,
+        longitude: row.longitude,
+        polygon: row
+            .polygon
+            .as_deref()
+            .and_then(|raw| serde_json::from_str(raw).ok()),
+        sort_order: row.sort_order,
+    }
+}
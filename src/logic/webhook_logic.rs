@@ -0,0 +1,516 @@
+// ============================================================================
+// src/logic/webhook_logic.rs - Outbound webhooks for partner sites
+// ============================================================================
+use crate::context::{UserContext, WebhookContext};
+use crate::errors::AppError;
+use crate::logic::UserCollectionLogic;
+use crate::models::database_models::{WebhookDeliveryRow, WebhookRow};
+use crate::models::user::{Claims, UserRole};
+use crate::models::webhook_models::{
+    PING, WEBHOOK_EVENT_TYPES, Webhook, WebhookPingResult, WebhookRequest, WebhookSendReport,
+};
+use chrono::{Duration, SecondsFormat, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::Url;
+use serde_json::{Value, json};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+const SEND_BATCH: i64 = 50;
+const MAX_ATTEMPTS: i64 = 8;
+// 30s, 1m, 2m ... between attempts, never more than 6 hours
+const BACKOFF_BASE_SECS: i64 = 30;
+const BACKOFF_MAX_SECS: i64 = 6 * 60 * 60;
+const DELIVERY_LOG_LIMIT: i64 = 100;
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+
+pub struct WebhookLogic {
+    repository: WebhookContext,
+    user_collection_logic: Arc<UserCollectionLogic>,
+    users: UserContext,
+    client: reqwest::Client,
+}
+
+impl WebhookLogic {
+    pub fn new(
+        repository: WebhookContext,
+        user_collection_logic: Arc<UserCollectionLogic>,
+        users: UserContext,
+    ) -> Self {
+        let client = client_builder().build().unwrap_or_default();
+
+        Self {
+            repository,
+            user_collection_logic,
+            users,
+            client,
+        }
+    }
+
+    ///Admins see every webhook, everyone else only their own
+    pub async fn get_webhooks(&self, claims: &Claims) -> Result<Vec<Webhook>, AppError> {
+        let rows = if is_admin(claims) {
+            self.repository.find_all().await?
+        } else {
+            self.repository.find_by_owner(&claims.sub).await?
+        };
+
+        rows.into_iter().map(|row| to_webhook(row, false)).collect()
+    }
+
+    pub async fn get_webhook(&self, id: i64, claims: &Claims) -> Result<Webhook, AppError> {
+        let row = self.find_owned(id, claims).await?;
+        to_webhook(row, false)
+    }
+
+    ///The signing secret is in the response, it is not shown again
+    pub async fn create_webhook(
+        &self,
+        request: WebhookRequest,
+        claims: &Claims,
+    ) -> Result<Webhook, AppError> {
+        let scope = Self::validate_request(&request, claims).await?;
+
+        let bytes: [u8; 32] = rand::random();
+        let secret = format!("whsec_{}", hex::encode(bytes));
+
+        let id = self
+            .repository
+            .create(
+                &claims.sub,
+                scope,
+                request.url.trim(),
+                &secret,
+                &serde_json::to_string(&request.events)?,
+                request.active,
+            )
+            .await?;
+
+        let row = self.repository.find_by_id(id).await?;
+        to_webhook(row, true)
+    }
+
+    pub async fn update_webhook(
+        &self,
+        id: i64,
+        request: WebhookRequest,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        self.find_owned(id, claims).await?;
+        let scope = Self::validate_request(&request, claims).await?;
+
+        let updated = self
+            .repository
+            .update(
+                id,
+                scope,
+                request.url.trim(),
+                &serde_json::to_string(&request.events)?,
+                request.active,
+            )
+            .await?;
+        if !updated {
+            return Err(AppError::NotFound("Webhook not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn delete_webhook(&self, id: i64, claims: &Claims) -> Result<(), AppError> {
+        self.find_owned(id, claims).await?;
+
+        let deleted = self.repository.delete(id).await?;
+        if !deleted {
+            return Err(AppError::NotFound("Webhook not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    ///Latest deliveries first
+    pub async fn get_deliveries(
+        &self,
+        id: i64,
+        claims: &Claims,
+    ) -> Result<Vec<WebhookDeliveryRow>, AppError> {
+        self.find_owned(id, claims).await?;
+        self.repository
+            .find_deliveries(id, DELIVERY_LOG_LIMIT)
+            .await
+    }
+
+    ///Sends a ping right away so the receiver can check its signature handling.
+    ///It shows up in the delivery log but is not retried.
+    pub async fn ping(&self, id: i64, claims: &Claims) -> Result<WebhookPingResult, AppError> {
+        let webhook = self.find_owned(id, claims).await?;
+
+        let payload = envelope(PING, json!({ "webhook_id": id }))?;
+        let delivery_id = self.repository.enqueue_delivery(id, PING, &payload).await?;
+
+        let (response_status, error) = match self.post(&webhook, delivery_id, PING, &payload).await
+        {
+            Ok(status) => {
+                self.repository.mark_delivered(delivery_id, status).await?;
+                (Some(status), None)
+            }
+            Err((status, error)) => {
+                self.repository
+                    .mark_failed(delivery_id, status, &error)
+                    .await?;
+                (status, Some(error))
+            }
+        };
+
+        Ok(WebhookPingResult {
+            delivery_id,
+            delivered: error.is_none(),
+            response_status,
+            error,
+        })
+    }
+
+    ///Queues a delivery for every active webhook that wants this change.
+    ///`owner_event_id` is the event the change belongs to, used for "owned" webhooks.
+    pub async fn emit(
+        &self,
+        event_type: &str,
+        owner_event_id: i64,
+        owner_microevent_id: Option<i64>,
+        data: Value,
+    ) -> Result<usize, AppError> {
+        let payload = envelope(event_type, data)?;
+        let mut queued = 0;
+
+        for webhook in self.repository.find_active().await? {
+            let events: Vec<String> = serde_json::from_str(&webhook.events).unwrap_or_default();
+            if !events.is_empty() && !events.iter().any(|e| e == event_type) {
+                continue;
+            }
+
+            if webhook.scope != "all" {
+                let collection = self
+                    .user_collection_logic
+                    .get(&webhook.owner_user_id)
+                    .await?;
+                let owns = collection.created_events.contains(&owner_event_id)
+                    || owner_microevent_id
+                        .is_some_and(|id| collection.created_microevents.contains(&id));
+                if !owns {
+                    continue;
+                }
+            }
+
+            self.repository
+                .enqueue_delivery(webhook.id, event_type, &payload)
+                .await?;
+            queued += 1;
+        }
+
+        Ok(queued)
+    }
+
+    ///One pass of the background delivery job
+    pub async fn send_due(&self) -> Result<WebhookSendReport, AppError> {
+        let mut report = WebhookSendReport::default();
+        let now = Utc::now();
+        let due = self
+            .repository
+            .find_due_deliveries(&now.to_rfc3339_opts(SecondsFormat::Secs, true), SEND_BATCH)
+            .await?;
+
+        let mut webhooks: HashMap<i64, WebhookRow> = HashMap::new();
+        for delivery in due {
+            let webhook = match webhooks.entry(delivery.webhook_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(self.repository.find_by_id(delivery.webhook_id).await?)
+                }
+            };
+
+            if !webhook.active {
+                self.repository
+                    .mark_failed(delivery.id, None, "Webhook is disabled")
+                    .await?;
+                report.failed += 1;
+                continue;
+            }
+
+            let (status, error) = match self
+                .post(
+                    webhook,
+                    delivery.id,
+                    &delivery.event_type,
+                    &delivery.payload,
+                )
+                .await
+            {
+                Ok(status) => {
+                    self.repository.mark_delivered(delivery.id, status).await?;
+                    report.delivered += 1;
+                    continue;
+                }
+                Err(failure) => failure,
+            };
+
+            let attempts = delivery.attempts + 1;
+            if attempts >= MAX_ATTEMPTS {
+                tracing::warn!(
+                    "webhook {} delivery {} failed for good after {} attempts: {}",
+                    webhook.id,
+                    delivery.id,
+                    attempts,
+                    error
+                );
+                self.repository
+                    .mark_failed(delivery.id, status, &error)
+                    .await?;
+                report.failed += 1;
+            } else {
+                let delay = (BACKOFF_BASE_SECS << (attempts - 1)).min(BACKOFF_MAX_SECS);
+                let next_attempt_at =
+                    (now + Duration::seconds(delay)).to_rfc3339_opts(SecondsFormat::Secs, true);
+                self.repository
+                    .mark_retry(delivery.id, &next_attempt_at, status, &error)
+                    .await?;
+                report.retrying += 1;
+            }
+        }
+
+        Ok(report)
+    }
+
+    ///POSTs the payload, any 2xx counts as delivered. The host is resolved
+    ///and checked again here, DNS may have changed since the webhook was saved,
+    ///and the request goes to the address that was checked.
+    async fn post(
+        &self,
+        webhook: &WebhookRow,
+        delivery_id: i64,
+        event_type: &str,
+        payload: &str,
+    ) -> Result<u16, (Option<u16>, String)> {
+        let url = Url::parse(&webhook.url).map_err(|e| (None, e.to_string()))?;
+        let addresses = resolve_host(&url)
+            .await
+            .map_err(|e| (None, e.to_string()))?;
+
+        let owner_is_admin = matches!(
+            self.users.get_user_role(&webhook.owner_user_id).await,
+            Ok(UserRole::Admin | UserRole::SuperAdmin)
+        );
+        if !owner_is_admin && !addresses.iter().all(|address| is_public_ip(address.ip())) {
+            return Err((
+                None,
+                "Webhook url no longer points to a public address".to_string(),
+            ));
+        }
+
+        let host = url.host_str().unwrap_or_default();
+        let client = if host.parse::<IpAddr>().is_ok() {
+            self.client.clone()
+        } else {
+            client_builder()
+                .resolve(host, addresses[0])
+                .build()
+                .map_err(|e| (None, e.to_string()))?
+        };
+
+        let timestamp = Utc::now().timestamp();
+
+        let response = client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "NomadEvents-Webhooks/1.0")
+            .header("X-Webhook-Event", event_type)
+            .header("X-Webhook-Delivery", delivery_id.to_string())
+            .header(
+                SIGNATURE_HEADER,
+                signature(&webhook.secret, timestamp, payload),
+            )
+            .body(payload.to_string())
+            .send()
+            .await
+            .map_err(|e| (None, e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(status.as_u16())
+        } else {
+            Err((
+                Some(status.as_u16()),
+                format!("Receiver answered {}", status),
+            ))
+        }
+    }
+
+    async fn find_owned(&self, id: i64, claims: &Claims) -> Result<WebhookRow, AppError> {
+        let row = self.repository.find_by_id(id).await?;
+        if !is_admin(claims) && row.owner_user_id != claims.sub {
+            // same answer as a missing webhook, ids are not secret but the config is
+            return Err(AppError::NotFound("Webhook not found".to_string()));
+        }
+
+        Ok(row)
+    }
+
+    ///Returns the scope to store
+    async fn validate_request(
+        request: &WebhookRequest,
+        claims: &Claims,
+    ) -> Result<&'static str, AppError> {
+        let url = reqwest::Url::parse(request.url.trim())
+            .map_err(|_| AppError::ValidationError("Webhook url is not a valid URL".to_string()))?;
+        let local = matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+        if url.scheme() != "https" && !(url.scheme() == "http" && local) {
+            return Err(AppError::ValidationError(
+                "Webhook url must use https".to_string(),
+            ));
+        }
+        // admins may point webhooks at internal services, nobody else may
+        if !is_admin(claims) {
+            check_public_host(&url).await?;
+        }
+
+        if let Some(unknown) = request
+            .events
+            .iter()
+            .find(|e| !WEBHOOK_EVENT_TYPES.contains(&e.as_str()))
+        {
+            return Err(AppError::ValidationError(format!(
+                "Unknown webhook event type {}, expected one of {}",
+                unknown,
+                WEBHOOK_EVENT_TYPES.join(", ")
+            )));
+        }
+
+        match (request.scope.as_deref(), is_admin(claims)) {
+            (Some("all") | None, true) => Ok("all"),
+            (Some("owned"), _) | (None, false) => Ok("owned"),
+            (Some("all"), false) => Err(AppError::Forbidden(
+                "Only admins can subscribe to every change".to_string(),
+            )),
+            (Some(other), _) => Err(AppError::ValidationError(format!(
+                "Unknown webhook scope {}, expected all or owned",
+                other
+            ))),
+        }
+    }
+}
+
+fn is_admin(claims: &Claims) -> bool {
+    claims.role == "admin" || claims.role == "super_admin"
+}
+
+///No redirects, a receiver could otherwise bounce us to an address we did not check
+fn client_builder() -> reqwest::ClientBuilder {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .redirect(reqwest::redirect::Policy::none())
+}
+
+///Every address the url's host resolves to, at least one
+async fn resolve_host(url: &Url) -> Result<Vec<SocketAddr>, AppError> {
+    let port = url.port_or_known_default().unwrap_or(443);
+    let host = url
+        .host_str()
+        .unwrap_or_default()
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let addresses: Vec<SocketAddr> = match host.parse::<IpAddr>() {
+        Ok(ip) => vec![SocketAddr::new(ip, port)],
+        Err(_) => tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|_| {
+                AppError::ValidationError(format!("Webhook host {} could not be resolved", host))
+            })?
+            .collect(),
+    };
+
+    if addresses.is_empty() {
+        return Err(AppError::ValidationError(format!(
+            "Webhook host {} could not be resolved",
+            host
+        )));
+    }
+
+    Ok(addresses)
+}
+
+///Rejects the host if any address is private, loopback, link-local or
+///otherwise not reachable on the public internet
+async fn check_public_host(url: &Url) -> Result<(), AppError> {
+    let addresses = resolve_host(url).await?;
+
+    if !addresses.iter().all(|address| is_public_ip(address.ip())) {
+        return Err(AppError::ValidationError(
+            "Webhook url must point to a public address".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b))) // carrier-grade NAT
+        }
+        IpAddr::V6(ip) => {
+            if let Some(mapped) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(mapped));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || (first & 0xfe00) == 0xfc00 // unique local
+                || (first & 0xffc0) == 0xfe80) // link-local
+        }
+    }
+}
+
+fn to_webhook(row: WebhookRow, with_secret: bool) -> Result<Webhook, AppError> {
+    Ok(Webhook {
+        id: row.id,
+        owner_user_id: row.owner_user_id,
+        scope: row.scope,
+        url: row.url,
+        events: serde_json::from_str(&row.events)?,
+        active: row.active,
+        created_at: row.created_at,
+        secret: with_secret.then_some(row.secret),
+    })
+}
+
+fn envelope(event_type: &str, data: Value) -> Result<String, AppError> {
+    Ok(serde_json::to_string(&json!({
+        "type": event_type,
+        "created_at": Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        "data": data,
+    }))?)
+}
+
+///"t=<unix seconds>,v1=<hex HMAC-SHA256 of "<t>.<body>">", the timestamp lets
+///receivers reject replays
+fn signature(secret: &str, timestamp: i64, payload: &str) -> String {
+    // HMAC takes keys of any length, new_from_slice can not fail here
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("any key length");
+    mac.update(format!("{}.{}", timestamp, payload).as_bytes());
+    format!(
+        "t={},v1={}",
+        timestamp,
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
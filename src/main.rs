@@ -27,19 +27,41 @@ mod logic;
 mod models;
 mod routes; // This imports and registers the trait impl
 
+use context::CalendarTokenContext;
 use context::CampingProfileContext;
+use context::EmailOutboxContext;
+use context::EmailVerificationContext;
 use context::EventContext;
+use context::EventSeriesContext;
 use context::EventTypeContext;
 use context::MicroeventContext;
+use context::MicroeventTrackContext;
+use context::NotificationContext;
+use context::RsvpContext;
+use context::SavedSearchContext;
 use context::UserCollectionContext;
 use context::UserContext;
+use context::VenueAreaContext;
+use context::WebhookContext;
 use custom_middleware::*;
+use logic::CalendarLogic;
 use logic::CampingProfileLogic;
+use logic::ChangeFeed;
+use logic::EventImportLogic;
 use logic::EventLogic;
+use logic::EventSeriesLogic;
 use logic::EventTypeLogic;
+use logic::MailLogic;
 use logic::MicroeventLogic;
+use logic::NotificationService;
+use logic::RsvpLogic;
+use logic::SavedSearchLogic;
+use logic::TrackLogic;
+use logic::TripLogic;
 use logic::UserCollectionLogic;
 use logic::UserLogic;
+use logic::VenueLogic;
+use logic::WebhookLogic;
 use routes::events::*;
 use routes::microevents::*;
 use routes::*;
@@ -64,6 +86,18 @@ pub struct AppState {
     pub oauth_states: Arc<Mutex<HashMap<String, std::time::Instant>>>,
     pub user_logic: Arc<UserLogic>,
     pub user_collection_logic: Arc<UserCollectionLogic>,
+    pub trip_logic: Arc<TripLogic>,
+    pub event_series_logic: Arc<EventSeriesLogic>,
+    pub calendar_logic: Arc<CalendarLogic>,
+    pub event_import_logic: Arc<EventImportLogic>,
+    pub saved_search_logic: Arc<SavedSearchLogic>,
+    pub notification_service: Arc<NotificationService>,
+    pub mail_logic: Arc<MailLogic>,
+    pub webhook_logic: Arc<WebhookLogic>,
+    pub change_feed: Arc<ChangeFeed>,
+    pub venue_logic: Arc<VenueLogic>,
+    pub track_logic: Arc<TrackLogic>,
+    pub rsvp_logic: Arc<RsvpLogic>,
 }
 
 #[tokio::main]
@@ -92,8 +126,11 @@ async fn main() {
         .await
         .expect("Failed to run migrations");
 
-    // Seed database with test data (uncomment to run once)
-    seed_all(&db).await.expect("Failed to seed database");
+    // Seed database with test data, only when SEED_DATABASE=true (see .env.example).
+    // Real data goes through POST /admin/event/import.
+    if env::var("SEED_DATABASE").is_ok_and(|v| v == "true") {
+        seed_all(&db).await.expect("Failed to seed database");
+    }
 
     // Build layers: Repository -> Service
     let eventcontext = EventContext::new(db.clone());
@@ -115,15 +152,218 @@ async fn main() {
         usercollectioncontext,
         eventcontext,
         microeventcontext,
+        MicroeventTrackContext::new(db.clone()),
     ));
     // 3. Now create EventLogic and MicroeventLogic with usercollectionlogic
+    let mailtransport =
+        logic::mail_transport::transport_from_env().expect("Failed to set up mail transport");
+    let port = env::var("PORT").unwrap_or_default();
+    let maillogic = Arc::new(MailLogic::new(
+        EmailOutboxContext::new(db.clone()),
+        EmailVerificationContext::new(db.clone()),
+        NotificationContext::new(db.clone()),
+        mailtransport,
+        env::var("MAIL_FROM").unwrap_or_else(|_| "noreply@localhost".to_string()),
+        env::var("PUBLIC_BASE_URL").unwrap_or_else(|_| format!("http://localhost:{}", port)),
+    ));
+    tracing::info!("mail transport: {}", maillogic.transport_name());
+    let notificationservice = Arc::new(NotificationService::new(
+        NotificationContext::new(db.clone()),
+        maillogic.clone(),
+    ));
+    let webhooklogic = Arc::new(WebhookLogic::new(
+        WebhookContext::new(db.clone()),
+        usercollectionlogic.clone(),
+        UserContext::new(db.clone()),
+    ));
+    let changefeed = Arc::new(ChangeFeed::new());
     let eventcontext2 = EventContext::new(db.clone());
-    let eventlogic = Arc::new(EventLogic::new(eventcontext2, usercollectionlogic.clone()));
+    let eventlogic = Arc::new(EventLogic::new(
+        eventcontext2,
+        usercollectionlogic.clone(),
+        notificationservice.clone(),
+        webhooklogic.clone(),
+        changefeed.clone(),
+    ));
+    let rsvplogic = Arc::new(RsvpLogic::new(
+        RsvpContext::new(db.clone()),
+        MicroeventContext::new(db.clone()),
+        usercollectionlogic.clone(),
+        notificationservice.clone(),
+    ));
     let microeventcontext2 = MicroeventContext::new(db.clone());
     let microeventlogic = Arc::new(MicroeventLogic::new(
         microeventcontext2,
+        EventContext::new(db.clone()),
+        VenueAreaContext::new(db.clone()),
+        MicroeventTrackContext::new(db.clone()),
+        usercollectionlogic.clone(),
+        webhooklogic.clone(),
+        changefeed.clone(),
+        rsvplogic.clone(),
+        notificationservice.clone(),
+    ));
+    let venuelogic = Arc::new(VenueLogic::new(
+        VenueAreaContext::new(db.clone()),
+        EventContext::new(db.clone()),
+        MicroeventContext::new(db.clone()),
+        usercollectionlogic.clone(),
+    ));
+    let tracklogic = Arc::new(TrackLogic::new(
+        MicroeventTrackContext::new(db.clone()),
+        EventContext::new(db.clone()),
         usercollectionlogic.clone(),
     ));
+    let triplogic = Arc::new(TripLogic::new(usercollectionlogic.clone()));
+    let eventseriescontext = EventSeriesContext::new(db.clone());
+    let eventseriesevents = EventContext::new(db.clone());
+    let eventserieslogic = Arc::new(EventSeriesLogic::new(
+        eventseriescontext,
+        eventseriesevents,
+        usercollectionlogic.clone(),
+        eventlogic.clone(),
+    ));
+    let calendarlogic = Arc::new(CalendarLogic::new(
+        CalendarTokenContext::new(db.clone()),
+        EventContext::new(db.clone()),
+        MicroeventContext::new(db.clone()),
+        RsvpContext::new(db.clone()),
+        usercollectionlogic.clone(),
+    ));
+
+    let eventimportlogic = Arc::new(EventImportLogic::new(
+        EventContext::new(db.clone()),
+        eventlogic.clone(),
+        usercollectionlogic.clone(),
+    ));
+
+    let savedsearchlogic = Arc::new(SavedSearchLogic::new(
+        SavedSearchContext::new(db.clone()),
+        notificationservice.clone(),
+        EventContext::new(db.clone()),
+        eventlogic.clone(),
+    ));
+
+    // Saved search alerts, checked in the background every SAVED_SEARCH_INTERVAL_SECS
+    let alert_interval = env::var("SAVED_SEARCH_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(900);
+    let alertlogic = savedsearchlogic.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(alert_interval));
+        loop {
+            interval.tick().await;
+            match alertlogic.run_alerts().await {
+                Ok(report) if report.notifications_created > 0 => tracing::info!(
+                    "saved search alerts: {} notifications from {} searches",
+                    report.notifications_created,
+                    report.searches_checked
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("saved search alerts failed: {}", e),
+            }
+        }
+    });
+
+    // Email outbox sender, retries with backoff are handled per message
+    let send_interval = env::var("MAIL_SEND_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(30);
+    let senderlogic = maillogic.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(send_interval));
+        loop {
+            interval.tick().await;
+            match senderlogic.send_due().await {
+                Ok(report) if report.sent + report.retrying + report.failed > 0 => {
+                    tracing::info!(
+                        "mail outbox: {} sent, {} retrying, {} failed",
+                        report.sent,
+                        report.retrying,
+                        report.failed
+                    )
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("mail outbox sender failed: {}", e),
+            }
+        }
+    });
+
+    // Webhook deliveries, retried with backoff like the mail outbox
+    let webhook_interval = env::var("WEBHOOK_SEND_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(15);
+    let deliverylogic = webhooklogic.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(webhook_interval));
+        loop {
+            interval.tick().await;
+            match deliverylogic.send_due().await {
+                Ok(report) if report.delivered + report.retrying + report.failed > 0 => {
+                    tracing::info!(
+                        "webhooks: {} delivered, {} retrying, {} failed",
+                        report.delivered,
+                        report.retrying,
+                        report.failed
+                    )
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("webhook delivery failed: {}", e),
+            }
+        }
+    });
+
+    // Email digests of unread notifications
+    let digest_interval = env::var("MAIL_DIGEST_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(86400);
+    let digestlogic = maillogic.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(digest_interval));
+        // restarts should not send everyone a second digest the same day
+        let min_gap = chrono::Duration::seconds(digest_interval as i64 / 2);
+        loop {
+            interval.tick().await;
+            match digestlogic.queue_digests(min_gap).await {
+                Ok(0) => {}
+                Ok(queued) => tracing::info!("mail digests: {} queued", queued),
+                Err(e) => tracing::warn!("mail digests failed: {}", e),
+            }
+        }
+    });
+
+    // Startup integrity check. Rows with broken columns or types are left out of
+    // every list endpoint, an unreadable legacy event_data blob is only reported.
+    match eventlogic.scan_integrity().await {
+        Ok(report) if report.broken.is_empty() => {
+            tracing::info!("integrity check: {} events OK", report.scanned)
+        }
+        Ok(report) => {
+            let unreadable_blobs = report
+                .broken
+                .iter()
+                .filter(|row| row.source == "event_data")
+                .count();
+            tracing::warn!(
+                "integrity check: {} of {} events can not be served, {} with a legacy blob unreadable",
+                report.broken.len() - unreadable_blobs,
+                report.scanned,
+                unreadable_blobs
+            );
+            for row in &report.broken {
+                tracing::warn!("  event {} ({}): {}", row.id, row.source, row.error);
+            }
+        }
+        Err(e) => tracing::warn!("integrity check failed to run: {}", e),
+    }
 
     let app_state = Arc::new(AppState {
         event_logic: eventlogic,
@@ -133,6 +373,18 @@ async fn main() {
         oauth_states: oauthstates,
         user_logic: userlogic,
         user_collection_logic: usercollectionlogic,
+        trip_logic: triplogic,
+        event_series_logic: eventserieslogic,
+        calendar_logic: calendarlogic,
+        event_import_logic: eventimportlogic,
+        saved_search_logic: savedsearchlogic,
+        notification_service: notificationservice,
+        mail_logic: maillogic,
+        webhook_logic: webhooklogic,
+        change_feed: changefeed,
+        venue_logic: venuelogic,
+        track_logic: tracklogic,
+        rsvp_logic: rsvplogic,
     });
 
     // Configure CORS - very permissive for development
@@ -147,6 +399,8 @@ async fn main() {
         .route("/", get(|| async { "Festival Events API" }))
         .route("/health", get(health_check))
         .route("/event/search", get(routes::events::search))
+        .route("/event/geojson", get(routes::events::geojson))
+        .route("/event/clusters", get(routes::events::clusters))
         .route(
             "/auth/google/signup",
             post(routes::auth::verify_google_create),
@@ -163,6 +417,28 @@ async fn main() {
             custom_middleware::api_key::validate_api_key,
         ));
 
+    // Calendar feeds and email links - calendar apps and mail clients can't send
+    // an API key or JWT, the secret token in the path is the credential
+    let calendar_routes = Router::new()
+        .route("/calendar/{token}", get(routes::calendar::feed))
+        .route("/verify-email/{token}", get(routes::email::verify))
+        .layer(middleware::from_fn(
+            custom_middleware::rate_limit::rate_limit_middleware,
+        ));
+
+    // Live schedule updates - the JWT may come as ?token= since browsers
+    // can't add an Authorization header to a WebSocket handshake or an EventSource
+    let ws_routes = Router::new()
+        .route("/ws", get(routes::live::ws))
+        .route("/sse", get(routes::live::sse))
+        .route_layer(middleware::from_fn_with_state(
+            db.clone(),
+            custom_middleware::auth_middleware::ws_auth_middleware,
+        ))
+        .layer(middleware::from_fn(
+            custom_middleware::rate_limit::rate_limit_middleware,
+        ));
+
     // 2. API Key protected routes (auth endpoints)
     //let api_key_routes = Router::new()
     //.route("/auth/google", post(routes::auth::google_auth))
@@ -196,6 +472,34 @@ async fn main() {
                 .put(routes::microevents::update)
                 .delete(routes::microevents::delete),
         )
+        .route("/microevent/{id}/cancel", post(routes::microevents::cancel))
+        .route(
+            "/microevent/{id}/restore",
+            post(routes::microevents::restore),
+        )
+        .route("/event/{id}/now", get(routes::microevents::get_now))
+        .route(
+            "/event/{id}/microevent/pending",
+            get(routes::microevents::get_pending),
+        )
+        .route(
+            "/microevent/{id}/approve",
+            post(routes::microevents::approve),
+        )
+        .route("/microevent/{id}/reject", post(routes::microevents::reject))
+        .route(
+            "/microevent/{id}/archive",
+            post(routes::microevents::archive),
+        )
+        .route(
+            "/microevent/{id}/unarchive",
+            post(routes::microevents::unarchive),
+        )
+        .route(
+            "/microevent/{id}/rsvp",
+            post(routes::rsvps::rsvp).delete(routes::rsvps::cancel),
+        )
+        .route("/microevent/{id}/attendees", get(routes::rsvps::attendees))
         .route(
             "/microevent/{id}/save",
             get(routes::usercollection::microevent_save_toggle),
@@ -212,6 +516,32 @@ async fn main() {
             "/event/{id}/favorite",
             get(routes::usercollection::event_favorite_toggle),
         )
+        .route(
+            "/event/{id}/schedule",
+            get(routes::usercollection::get_personal_schedule),
+        )
+        .route("/event/{id}/schedule/grid", get(routes::venues::get_grid))
+        .route(
+            "/event/{id}/areas",
+            get(routes::venues::get_all).post(routes::venues::create),
+        )
+        .route("/event/{id}/areas/map", get(routes::venues::get_map))
+        .route(
+            "/event/{id}/microevent/map",
+            get(routes::venues::get_microevent_map),
+        )
+        .route(
+            "/event/{id}/areas/{area_id}",
+            put(routes::venues::update).delete(routes::venues::delete),
+        )
+        .route(
+            "/event/{id}/tracks",
+            get(routes::tracks::get_all).post(routes::tracks::create),
+        )
+        .route(
+            "/event/{id}/tracks/{track_id}",
+            put(routes::tracks::update).delete(routes::tracks::delete),
+        )
         .route("/usercollection", get(routes::usercollection::get))
         .route("/usercollection/sync", post(routes::usercollection::sync))
         .route(
@@ -238,6 +568,72 @@ async fn main() {
             "/user/saved/microevents",
             get(routes::usercollection::get_saved_microevents),
         )
+        .route(
+            "/user/calendar/token",
+            post(routes::calendar::create_token).delete(routes::calendar::revoke_tokens),
+        )
+        .route(
+            "/user/searches",
+            get(routes::saved_searches::get_all).post(routes::saved_searches::create),
+        )
+        .route(
+            "/user/searches/{id}",
+            put(routes::saved_searches::update).delete(routes::saved_searches::delete),
+        )
+        .route("/user/searches/{id}/run", get(routes::saved_searches::run))
+        .route("/user/notifications", get(routes::notifications::get_all))
+        .route(
+            "/user/notifications/read-all",
+            post(routes::notifications::mark_all_read),
+        )
+        .route(
+            "/user/notifications/preferences",
+            get(routes::notifications::get_preferences)
+                .put(routes::notifications::update_preferences),
+        )
+        .route(
+            "/user/notifications/{id}/read",
+            post(routes::notifications::mark_read),
+        )
+        .route(
+            "/user/email/verify",
+            post(routes::email::request_verification),
+        )
+        .route(
+            "/webhooks",
+            get(routes::webhooks::get_all).post(routes::webhooks::create),
+        )
+        .route(
+            "/webhooks/{id}",
+            get(routes::webhooks::get)
+                .put(routes::webhooks::update)
+                .delete(routes::webhooks::delete),
+        )
+        .route(
+            "/webhooks/{id}/deliveries",
+            get(routes::webhooks::get_deliveries),
+        )
+        .route("/webhooks/{id}/ping", post(routes::webhooks::ping))
+        .route("/user/trip", get(routes::trip::get_trip))
+        .route("/user/trip/gpx", get(routes::trip::get_trip_gpx))
+        .route(
+            "/event/{id}/next-edition",
+            post(routes::event_series::clone_to_next_edition),
+        )
+        .route(
+            "/event/{id}/series",
+            put(routes::event_series::set_event_series),
+        )
+        .route("/series", get(routes::event_series::get_all))
+        .route("/series/{id}", get(routes::event_series::get))
+        .route(
+            "/series/{id}/favorite",
+            get(routes::event_series::favorite_toggle),
+        )
+        .route(
+            "/user/favorites/series",
+            get(routes::event_series::get_favorite_series),
+        )
         .route("/eventtype", get(routes::event_type::get_all))
         .route("/eventtype/{id}", get(routes::event_type::get))
         .route("/campingprofile", get(routes::camping_profiles::get_all))
@@ -266,12 +662,27 @@ async fn main() {
             "/eventtype/{id}",
             put(routes::event_type::update).delete(routes::event_type::delete),
         )
+        .route("/series", post(routes::event_series::create))
+        .route(
+            "/series/{id}",
+            put(routes::event_series::update).delete(routes::event_series::delete),
+        )
         .route("/campingprofile", post(routes::camping_profiles::create))
         .route(
             "/campingprofile/{id}",
             put(routes::camping_profiles::update).delete(routes::camping_profiles::delete),
         )
         .route("/event/{id}", delete(routes::events::delete))
+        .route("/admin/event/import", post(routes::events::import))
+        .route("/event/integrity", get(routes::events::integrity_scan))
+        .route(
+            "/event/upgrade-data",
+            post(routes::events::upgrade_stored_data),
+        )
+        .route(
+            "/campingprofile/upgrade-data",
+            post(routes::camping_profiles::upgrade_stored_data),
+        )
         //check authorization
         .route_layer(middleware::from_fn(
             custom_middleware::auth_middleware::require_super_admin,
@@ -286,6 +697,8 @@ async fn main() {
     let app = Router::new()
         //.merge(auth_routes)
         .merge(public_routes)
+        .merge(calendar_routes)
+        .merge(ws_routes)
         //.merge(api_key_routes)
         .merge(jwt_routes)
         .merge(admin_routes)
@@ -19,13 +19,105 @@ pub struct EventRow {
     pub start_date: Option<String>,
     pub end_date: Option<String>,
     pub camping_allowed: Option<bool>,
-    pub event_data: String, // Still stores full event as JSON
+    pub series_id: Option<i64>,
+    pub next_edition_id: Option<i64>, // subquery, the following edition in the same series
 
-    // Event type fields from JOIN --- This seems very wrong. It seems to be doing more work than nessicary, Need something closer to a VM but this seems like it will store these again?
+    // Location / date details, promoted out of event_data
+    pub address: String,
+    pub venue_name: Option<String>,
+    pub parking_info: Option<String>,
+    pub single_day: bool,
+    pub early_arrival_available: bool,
+    pub early_arrival_date: Option<String>,
+    pub late_departure_available: bool,
+    pub require_microevent_approval: bool,
+    pub time_zone: Option<String>,
+
+    // Event type fields from JOIN, only read - nothing is stored twice
     pub event_type_name: String,
     pub event_type_description: String,
     pub event_type_map_indicator: String,
     pub event_type_category: String,
+
+    // Typed detail tables from LEFT JOINs, every column is NULL when the row is missing
+    #[sqlx(flatten)]
+    pub amenities: EventAmenitiesRow,
+    #[sqlx(flatten)]
+    pub camping: EventCampingRow,
+    #[sqlx(flatten)]
+    pub rv: EventRvOptionsRow,
+    #[sqlx(flatten)]
+    pub generator: EventGeneratorOptionsRow,
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct EventAmenitiesRow {
+    pub amenities_event_id: Option<i64>, // NULL when the event has no amenities
+    pub bathrooms: Option<bool>,
+    pub showers: Option<bool>,
+    pub potable_water: Option<bool>,
+    pub wifi: Option<bool>,
+    pub cell_service_quality: Option<String>,
+    pub firewood_available: Option<bool>,
+    pub ice_available: Option<bool>,
+    pub trash_service: Option<bool>,
+    pub recycling: Option<bool>,
+    pub laundry: Option<bool>,
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct EventCampingRow {
+    pub camping_event_id: Option<i64>, // NULL when the event has no camping info
+    pub walking_distance: Option<bool>,
+    pub tent_camping: Option<bool>,
+    pub campsite_reservations_required: Option<bool>,
+    pub primitive_camping: Option<bool>,
+    pub developed_campsites: Option<bool>,
+    pub max_stay_nights: Option<i64>,
+    pub pet_friendly: Option<bool>,
+    pub quiet_hours: Option<String>,
+    pub fires_allowed: Option<bool>,
+    pub van_camping: Option<bool>,
+    pub car_camping: Option<bool>,
+    pub truck_camping: Option<bool>,
+    pub rooftop_tent_allowed: Option<bool>,
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct EventRvOptionsRow {
+    pub rv_allowed: Option<bool>,
+    pub class_a_allowed: Option<bool>,
+    pub class_b_allowed: Option<bool>,
+    pub class_c_allowed: Option<bool>,
+    pub travel_trailers_allowed: Option<bool>,
+    pub fifth_wheel_allowed: Option<bool>,
+    pub max_length_feet: Option<i64>,
+    pub max_width_feet: Option<i64>,
+    pub hookups_available: Option<bool>,
+    pub hookup_electric: Option<bool>,
+    pub hookup_water: Option<bool>,
+    pub hookup_sewer: Option<bool>,
+    pub hookup_amp_service: Option<String>,
+    pub dump_station: Option<bool>,
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct EventGeneratorOptionsRow {
+    pub generator_event_id: Option<i64>, // NULL when the event has no generator options
+    pub generators_allowed: Option<bool>,
+    pub max_decibel_limit: Option<i64>,
+    pub inverter_generators_only: Option<bool>,
+    pub propane_generators_allowed: Option<bool>,
+    pub gasoline_generators_allowed: Option<bool>,
+    pub diesel_generators_allowed: Option<bool>,
+    pub designated_generator_areas: Option<bool>,
+    pub distance_from_neighbors_feet: Option<i64>,
+    pub fuel_storage_restrictions: Option<String>,
+    pub has_quiet_hours: Option<bool>,
+    pub quiet_hours_all_day: Option<bool>,
+    pub quiet_hours_start: Option<String>,
+    pub quiet_hours_end: Option<String>,
+    pub quiet_hours_days: Option<String>, // comma separated
 }
 
 //####################################################################
@@ -67,6 +159,78 @@ pub struct EventTypeRow {
     pub map_indicator: String,
     pub category: String,
 }
+//####################################################################
+//Event Series model
+//####################################################################
+#[derive(sqlx::FromRow)]
+pub struct EventSeriesRow {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub website: Option<String>,
+}
+//####################################################################
+//Saved search / notification models
+//####################################################################
+#[derive(sqlx::FromRow)]
+pub struct SavedSearchRow {
+    pub id: i64,
+    pub user_id: String,
+    pub name: String,
+    pub query: String, // EventQueryParams JSON
+    pub alerts_enabled: bool,
+    pub last_checked_at: String,
+    pub checked_event_ids: String, // JSON array of event ids
+    pub created_at: String,
+}
+
+#[derive(sqlx::FromRow, Serialize, Debug, Clone)]
+pub struct NotificationRow {
+    pub id: i64,
+    pub user_id: String,
+    pub category: String,
+    pub title: String,
+    pub body: String,
+    pub event_id: Option<i64>,
+    pub created_at: String,
+    pub read_at: Option<String>,
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct EmailOutboxRow {
+    pub id: i64,
+    pub to_address: String,
+    pub message_type: String,
+    pub subject: String,
+    pub body: String,
+    pub attempts: i64,
+}
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct WebhookRow {
+    pub id: i64,
+    pub owner_user_id: String,
+    pub scope: String,
+    pub url: String,
+    pub secret: String,
+    pub events: String, // JSON array of event types
+    pub active: bool,
+    pub created_at: String,
+}
+
+#[derive(sqlx::FromRow, Serialize, Debug, Clone)]
+pub struct WebhookDeliveryRow {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event_type: String,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i64,
+    pub response_status: Option<i64>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub delivered_at: Option<String>,
+}
+
 //####################################################################
 //User model
 //####################################################################
@@ -122,6 +286,7 @@ pub struct UserEventDataRow {
     pub saved_microevents: Vec<i64>,
     pub created_events: Vec<i64>,
     pub created_microevents: Vec<i64>,
+    pub favorite_series: Vec<i64>, // Event series IDs
 }
 
 //####################################################################
@@ -200,3 +365,17 @@ pub struct CreatorStats {
     pub avg_favorites_per_event: f64,
     pub creator_score: f64, // Custom metric
 }
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct VenueAreaRow {
+    pub id: i64,
+    pub event_id: i64,
+    pub name: String,
+    pub kind: String,
+    pub description: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub polygon: Option<String>, // JSON array of [longitude, latitude]
+    pub sort_order: i64,
+    pub created_at: String,
+}
@@ -3,6 +3,7 @@
 // ============================================================================
 use serde::{Deserialize, Serialize};
 //use crate::models::event_models::CampingInfo;
+use crate::errors::AppError;
 use crate::models::database_models::EventRow;
 use crate::models::{event_models::*, microevents_models::Microevent};
 use chrono::{DateTime, Utc};
@@ -35,13 +36,48 @@ pub struct EventResponseDto {
     // Simplified response
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct EventQueryParams {
     pub event_type: Option<i64>,
     pub camping_allowed: Option<bool>,
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
     pub radius_miles: Option<f64>,
+    // GeoJSON order: "min_lon,min_lat,max_lon,max_lat"
+    pub bbox: Option<String>,
+
+    // Amenity / camping filters - only applied when set
+    pub showers: Option<bool>,
+    pub potable_water: Option<bool>,
+    pub wifi: Option<bool>,
+    pub pet_friendly: Option<bool>,
+    pub fires_allowed: Option<bool>,
+    pub hookups_available: Option<bool>,
+    pub dump_station: Option<bool>,
+}
+
+impl EventQueryParams {
+    /// Parsed bbox as (min_lon, min_lat, max_lon, max_lat)
+    pub fn bounding_box(&self) -> Result<Option<(f64, f64, f64, f64)>, AppError> {
+        let Some(bbox) = &self.bbox else {
+            return Ok(None);
+        };
+
+        let invalid = || {
+            AppError::ValidationError("bbox must be min_lon,min_lat,max_lon,max_lat".to_string())
+        };
+        let parts = bbox
+            .split(',')
+            .map(|part| part.trim().parse::<f64>().map_err(|_| invalid()))
+            .collect::<Result<Vec<f64>, AppError>>()?;
+
+        match parts[..] {
+            [min_lon, min_lat, max_lon, max_lat] if min_lon <= max_lon && min_lat <= max_lat => {
+                Ok(Some((min_lon, min_lat, max_lon, max_lat)))
+            }
+            _ => Err(invalid()),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -55,6 +91,61 @@ pub struct CreateEventRequest {
     pub camping_info: Option<CampingInfo>,        // User can customize after applying template
 }
 
+// POST /admin/event/import - either events (JSON rows) or csv
+#[derive(Debug, Deserialize)]
+pub struct ImportRequest {
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub skip_duplicates: bool, // otherwise a duplicate is a row error and fails the batch
+    pub events: Option<Vec<serde_json::Value>>, // same shape as POST /event
+    pub csv: Option<String>,                    // header row first
+    // CSV header -> NomEvent field path, e.g. "Start" -> "date_info.start_date".
+    // Headers that are not mapped are used as the path as-is.
+    #[serde(default)]
+    pub columns: std::collections::HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub total_rows: usize,
+    pub valid_rows: usize,
+    pub created_ids: Vec<i64>,
+    pub duplicates: Vec<ImportRowError>,
+    pub errors: Vec<ImportRowError>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowError {
+    pub row: usize, // 1-based, not counting the CSV header
+    pub name: Option<String>,
+    pub error: String,
+}
+
+// Events that are stored but can not be served
+#[derive(Debug, Serialize)]
+pub struct IntegrityReport {
+    pub scanned: usize,
+    pub broken: Vec<BrokenEventRow>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BrokenEventRow {
+    pub id: i64,
+    pub source: String, // "columns" or "event_type" (not served), "event_data" (legacy blob only)
+    pub error: String,
+}
+
+// Result of rewriting stored JSON to the current schema_version
+#[derive(Debug, Serialize)]
+pub struct SchemaUpgradeReport {
+    pub schema_version: u64,
+    pub scanned: usize,
+    pub upgraded: usize,
+    pub failed: Vec<i64>, // rows that could not be read, left untouched
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CampingProfileListResponse {
     pub id: i64,
@@ -74,18 +165,31 @@ pub struct EventResponse {
     pub location_info: Location,
     pub amenities: Option<Amenities>,
     pub camping_info: Option<CampingInfo>,
+    pub series_id: Option<i64>,
+    pub next_edition_id: Option<i64>,
+    pub require_microevent_approval: bool,
     //pub is_favorite: bool,
     //pub is_saved: bool,
 }
 
+// A series with all of its editions, oldest first
+#[derive(Debug, Serialize)]
+pub struct EventSeriesResponse {
+    #[serde(flatten)]
+    pub series: EventSeries,
+    pub editions: Vec<EventResponse>,
+}
+
 // Helper to convert EventRow to EventResponse -moving list of saved and favorites to local storage
+// Built from the typed columns, event_data is no longer parsed per row.
 impl EventResponse {
     pub fn from_row(
         row: EventRow,
         //user_favorites: &[i64],
         //user_saved: &[i64],
-    ) -> Result<Self, serde_json::Error> {
-        let event: NomEvent = serde_json::from_str(&row.event_data)?;
+    ) -> Result<Self, AppError> {
+        let start_date = parse_date_column(row.id, "start_date", row.start_date.as_deref())?;
+        let end_date = parse_date_column(row.id, "end_date", row.end_date.as_deref())?;
 
         // Check if this event's ID is in the user's favorites list
         //let is_favorite: bool = user_favorites.contains(&row.id);
@@ -93,10 +197,94 @@ impl EventResponse {
         // Check if this event's ID is in the user's saved list
         //let is_saved: bool = user_saved.contains(&row.id);
 
+        let amenities = row.amenities.amenities_event_id.map(|_| {
+            let a = &row.amenities;
+            Amenities {
+                bathrooms: a.bathrooms.unwrap_or(false),
+                showers: a.showers.unwrap_or(false),
+                potable_water: a.potable_water.unwrap_or(false),
+                wifi: a.wifi.unwrap_or(false),
+                cell_service_quality: a.cell_service_quality.clone(),
+                firewood_available: a.firewood_available.unwrap_or(false),
+                ice_available: a.ice_available.unwrap_or(false),
+                trash_service: a.trash_service.unwrap_or(false),
+                recycling: a.recycling.unwrap_or(false),
+                laundry: a.laundry.unwrap_or(false),
+            }
+        });
+
+        let generator_options = row.generator.generator_event_id.map(|_| {
+            let g = &row.generator;
+            GeneratorOptions {
+                generators_allowed: g.generators_allowed.unwrap_or(false),
+                quiet_hours: g
+                    .has_quiet_hours
+                    .unwrap_or(false)
+                    .then(|| GeneratorQuietHours {
+                        all_day_restriction: g.quiet_hours_all_day.unwrap_or(false),
+                        start_time: g.quiet_hours_start.clone(),
+                        end_time: g.quiet_hours_end.clone(),
+                        days_of_week: g
+                            .quiet_hours_days
+                            .as_ref()
+                            .map(|days| days.split(',').map(|day| day.to_string()).collect()),
+                    }),
+                max_decibel_limit: g.max_decibel_limit.map(|v| v as u32),
+                inverter_generators_only: g.inverter_generators_only.unwrap_or(false),
+                propane_generators_allowed: g.propane_generators_allowed.unwrap_or(false),
+                gasoline_generators_allowed: g.gasoline_generators_allowed.unwrap_or(false),
+                diesel_generators_allowed: g.diesel_generators_allowed.unwrap_or(false),
+                designated_generator_areas: g.designated_generator_areas.unwrap_or(false),
+                distance_from_neighbors_feet: g.distance_from_neighbors_feet.map(|v| v as u32),
+                fuel_storage_restrictions: g.fuel_storage_restrictions.clone(),
+            }
+        });
+
+        let camping_info = row.camping.camping_event_id.map(|_| {
+            let c = &row.camping;
+            let rv = &row.rv;
+            CampingInfo {
+                camping_allowed: row.camping_allowed.unwrap_or(false),
+                walking_distance: c.walking_distance.unwrap_or(false),
+                tent_camping: c.tent_camping.unwrap_or(false),
+                rv_camping: RvCampingOptions {
+                    allowed: rv.rv_allowed.unwrap_or(false),
+                    class_a_allowed: rv.class_a_allowed.unwrap_or(false),
+                    class_b_allowed: rv.class_b_allowed.unwrap_or(false),
+                    class_c_allowed: rv.class_c_allowed.unwrap_or(false),
+                    travel_trailers_allowed: rv.travel_trailers_allowed.unwrap_or(false),
+                    fifth_wheel_allowed: rv.fifth_wheel_allowed.unwrap_or(false),
+                    max_length_feet: rv.max_length_feet.map(|v| v as u32),
+                    max_width_feet: rv.max_width_feet.map(|v| v as u32),
+                    hookups_available: rv.hookups_available.unwrap_or(false).then(|| Hookups {
+                        electric: rv.hookup_electric.unwrap_or(false),
+                        water: rv.hookup_water.unwrap_or(false),
+                        sewer: rv.hookup_sewer.unwrap_or(false),
+                        amp_service: rv.hookup_amp_service.clone(),
+                    }),
+                    dump_station: rv.dump_station.unwrap_or(false),
+                },
+                vehicle_camping: VehicleCampingOptions {
+                    van_camping: c.van_camping.unwrap_or(false),
+                    car_camping: c.car_camping.unwrap_or(false),
+                    truck_camping: c.truck_camping.unwrap_or(false),
+                    rooftop_tent_allowed: c.rooftop_tent_allowed.unwrap_or(false),
+                },
+                campsite_reservations_required: c.campsite_reservations_required.unwrap_or(false),
+                primitive_camping: c.primitive_camping.unwrap_or(false),
+                developed_campsites: c.developed_campsites.unwrap_or(false),
+                max_stay_nights: c.max_stay_nights.map(|v| v as u32),
+                pet_friendly: c.pet_friendly.unwrap_or(false),
+                quiet_hours: c.quiet_hours.clone(),
+                fires_allowed: c.fires_allowed.unwrap_or(false),
+                generator_options,
+            }
+        });
+
         Ok(EventResponse {
             id: Some(row.id),
-            name: event.name,
-            description: event.description,
+            name: row.name,
+            description: row.description,
             event_type: EventType {
                 id: Some(row.event_type_id),
                 name: row.event_type_name,
@@ -104,11 +292,28 @@ impl EventResponse {
                 map_indicator: row.event_type_map_indicator,
                 category: row.event_type_category,
             },
-            website: event.website,
-            date_info: event.date_info,
-            location_info: event.location_info,
-            amenities: event.amenities,
-            camping_info: event.camping_info,
+            website: row.website,
+            date_info: EventDate {
+                start_date,
+                end_date,
+                single_day: row.single_day,
+                early_arrival_available: row.early_arrival_available,
+                early_arrival_date: row.early_arrival_date,
+                late_departure_available: row.late_departure_available,
+            },
+            location_info: Location {
+                address: row.address,
+                longitude: row.longitude.unwrap_or_default(),
+                latitude: row.latitude.unwrap_or_default(),
+                venue_name: row.venue_name,
+                parking_info: row.parking_info,
+                time_zone: row.time_zone,
+            },
+            amenities,
+            camping_info,
+            series_id: row.series_id,
+            next_edition_id: row.next_edition_id,
+            require_microevent_approval: row.require_microevent_approval,
             //is_favorite,
             //is_saved,
         })
@@ -120,6 +325,52 @@ impl EventResponse {
     //}
 }
 
+impl EventResponse {
+    /// Converts a batch of rows, leaving out any that fail.
+    /// With EVENT_STRICT_MODE=true each dropped row is logged with its id,
+    /// GET /event/integrity lists them all.
+    pub fn from_rows(rows: Vec<EventRow>) -> Vec<Self> {
+        let strict = strict_mode();
+
+        rows.into_iter()
+            .filter_map(|row| {
+                let id = row.id;
+                match Self::from_row(row) {
+                    Ok(event) => Some(event),
+                    Err(e) => {
+                        if strict {
+                            tracing::warn!("dropping event {} from response: {}", id, e);
+                        }
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+fn strict_mode() -> bool {
+    std::env::var("EVENT_STRICT_MODE")
+        .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+        .unwrap_or(false)
+}
+
+fn parse_date_column(
+    event_id: i64,
+    column: &str,
+    value: Option<&str>,
+) -> Result<Option<DateTime<Utc>>, AppError> {
+    match value {
+        None => Ok(None),
+        Some(raw) => parse_event_date(raw).map(Some).ok_or_else(|| {
+            AppError::SerializationError(format!(
+                "Event {} has an unreadable {}: '{}'",
+                event_id, column, raw
+            ))
+        }),
+    }
+}
+
 pub struct EventRequest {
     pub id: Option<i64>,
     pub name: String,
@@ -261,4 +512,5 @@ pub struct UserCollection {
     pub saved_microevents: Option<Vec<i64>>,
     pub created_events: Option<Vec<i64>>,
     pub created_microevents: Option<Vec<i64>>,
+    pub favorite_series: Option<Vec<i64>>,
 }
@@ -0,0 +1,117 @@
+use serde::Serialize;
+
+///A rendered message as handed to a MailTransport
+#[derive(Debug, Clone, Serialize)]
+pub struct OutgoingEmail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+///A rendered message on its way into the outbox
+#[derive(Debug, Clone)]
+pub struct QueuedEmail {
+    pub to: String,
+    pub message_type: &'static str,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DigestItem {
+    pub title: String,
+    pub body: String,
+}
+
+///Every kind of email we send. message_type is stored on the outbox row.
+#[derive(Debug, Clone)]
+pub enum EmailTemplate {
+    EmailVerification {
+        user_name: String,
+        link: String,
+    },
+    EventChanged {
+        user_name: String,
+        title: String,
+        summary: String,
+        link: Option<String>,
+    },
+    Digest {
+        user_name: String,
+        items: Vec<DigestItem>,
+    },
+}
+
+impl EmailTemplate {
+    pub fn message_type(&self) -> &'static str {
+        match self {
+            EmailTemplate::EmailVerification { .. } => "email_verification",
+            EmailTemplate::EventChanged { .. } => "event_changed",
+            EmailTemplate::Digest { .. } => "digest",
+        }
+    }
+
+    pub fn queued(&self, to: &str) -> QueuedEmail {
+        let (subject, body) = self.render();
+        QueuedEmail {
+            to: to.to_string(),
+            message_type: self.message_type(),
+            subject,
+            body,
+        }
+    }
+
+    ///Subject and plain text body
+    pub fn render(&self) -> (String, String) {
+        match self {
+            EmailTemplate::EmailVerification { user_name, link } => (
+                "Confirm your email address".to_string(),
+                format!(
+                    "Hi {},\n\n\
+                     Please confirm this email address for your Nomad Events account:\n\n\
+                     {}\n\n\
+                     The link is valid for 24 hours. If you did not ask for this, you can ignore this email.\n",
+                    user_name, link
+                ),
+            ),
+            EmailTemplate::EventChanged {
+                user_name,
+                title,
+                summary,
+                link,
+            } => {
+                let mut body = format!("Hi {},\n\n{}\n\n", user_name, summary);
+                if let Some(link) = link {
+                    body.push_str(&format!("See the latest details: {}\n\n", link));
+                }
+                body.push_str("You can turn these emails off in your notification preferences.\n");
+                (title.clone(), body)
+            }
+            EmailTemplate::Digest { user_name, items } => {
+                let mut body = format!(
+                    "Hi {},\n\nHere is what happened since your last digest:\n\n",
+                    user_name
+                );
+                for item in items {
+                    body.push_str(&format!("* {}\n  {}\n\n", item.title, item.body));
+                }
+                body.push_str("You can turn these emails off in your notification preferences.\n");
+
+                let subject = if items.len() == 1 {
+                    "Your Nomad Events digest: 1 update".to_string()
+                } else {
+                    format!("Your Nomad Events digest: {} updates", items.len())
+                };
+                (subject, body)
+            }
+        }
+    }
+}
+
+///Result of one pass of the outbox sender
+#[derive(Debug, Serialize, Default)]
+pub struct MailSendReport {
+    pub sent: usize,
+    pub retrying: usize,
+    pub failed: usize,
+}
@@ -1,5 +1,6 @@
 ///Nomadic Event, This is the base class/model/struct for the events
-use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Deserializer, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -23,6 +24,11 @@ pub struct NomEvent {
     pub camping_info: Option<CampingInfo>,
     #[serde(default)]
     pub archive: bool,
+    #[serde(default)]
+    pub series_id: Option<i64>,
+    ///Microevents from anyone but the owner wait for the owner's approval
+    #[serde(default)]
+    pub require_microevent_approval: bool,
 }
 
 fn deserialize_optional_date<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
@@ -31,24 +37,31 @@ where
 {
     let s: Option<String> = Option::deserialize(deserializer)?;
     match s {
-        Some(date_str) => {
-            // Try parsing as DateTime first
-            if let Ok(dt) = DateTime::parse_from_rfc3339(&date_str) {
-                return Ok(Some(dt.with_timezone(&Utc)));
-            }
-
-            // If that fails, try parsing as just a date (YYYY-MM-DD)
-            if let Ok(naive_date) = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d") {
-                let dt = Utc.from_utc_datetime(&naive_date.and_hms_opt(0, 0, 0).unwrap());
-                return Ok(Some(dt));
-            }
-
-            Err(serde::de::Error::custom("Invalid date format"))
-        }
+        Some(date_str) => parse_event_date(&date_str)
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom("Invalid date format")),
         None => Ok(None),
     }
 }
 
+///Parses the date formats we have stored over time, used for both JSON and the date columns
+pub fn parse_event_date(date_str: &str) -> Option<DateTime<Utc>> {
+    // Try parsing as DateTime first
+    if let Ok(dt) = DateTime::parse_from_rfc3339(date_str) {
+        return Some(dt.with_timezone(&Utc));
+    }
+
+    // If that fails, try parsing as just a date (YYYY-MM-DD)
+    if let Ok(naive_date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
+        return Some(Utc.from_utc_datetime(&naive_date.and_hms_opt(0, 0, 0)?));
+    }
+
+    // Older rows wrote the columns with DateTime's Display ("2025-06-01 00:00:00 UTC")
+    NaiveDateTime::parse_from_str(date_str, "%Y-%m-%d %H:%M:%S%.f UTC")
+        .ok()
+        .map(|naive| Utc.from_utc_datetime(&naive))
+}
+
 ///Self explanitory
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct EventDate {
@@ -65,6 +78,75 @@ pub struct EventDate {
     pub late_departure_available: bool,
 }
 
+impl EventDate {
+    ///Extra night before start_date, 0 when early arrival isn't offered
+    pub fn early_arrival_nights(&self) -> i64 {
+        if !self.early_arrival_available {
+            return 0;
+        }
+
+        // early_arrival_date is free text, fall back to one night when it isn't a usable date
+        let early = self
+            .early_arrival_date
+            .as_deref()
+            .and_then(parse_loose_date);
+
+        match (early, self.start_date) {
+            (Some(early), Some(start)) => (start.date_naive() - early).num_days().max(0),
+            _ => 1,
+        }
+    }
+
+    pub fn late_departure_nights(&self) -> i64 {
+        if self.late_departure_available { 1 } else { 0 }
+    }
+
+    ///First and last moment anyone can be on site, whole days in the event's time
+    ///zone from the early arrival day to the end of the late departure day.
+    ///None without a start_date.
+    pub fn attendance_window(&self, tz: Tz) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let start = local_date(self.start_date?, tz);
+        // Single day events (or ones missing an end) are over the day they start
+        let end = self.end_date.map_or(start, |end| local_date(end, tz));
+
+        let first_day = start - chrono::Duration::days(self.early_arrival_nights());
+        let after_last_day = end + chrono::Duration::days(self.late_departure_nights() + 1);
+
+        Some((
+            local_midnight(first_day, tz)?,
+            local_midnight(after_last_day, tz)?,
+        ))
+    }
+}
+
+///When the day starts in tz, as UTC. Where DST skips midnight the day starts
+///at the first hour that exists.
+pub fn local_midnight(day: NaiveDate, tz: Tz) -> Option<DateTime<Utc>> {
+    (0..3)
+        .find_map(|hour| {
+            tz.from_local_datetime(&day.and_hms_opt(hour, 0, 0)?)
+                .earliest()
+        })
+        .map(|start| start.with_timezone(&Utc))
+}
+
+///The calendar day of an event date in the event's time zone. Dates entered
+///without a time are stored as midnight UTC and keep that day as they are.
+pub fn local_date(value: DateTime<Utc>, tz: Tz) -> NaiveDate {
+    if value.time() == NaiveTime::MIN {
+        value.date_naive()
+    } else {
+        value.with_timezone(&tz).date_naive()
+    }
+}
+
+fn parse_loose_date(value: &str) -> Option<NaiveDate> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc).date_naive());
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
 ///Is this a Ren Faire, a music festival, car show, or something new?
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EventType {
@@ -79,6 +161,22 @@ pub struct EventType {
     pub category: String,
 }
 
+///Groups the yearly editions of the same festival
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EventSeries {
+    pub id: Option<i64>,
+    #[serde(default)]
+    pub name: String,
+    pub description: Option<String>,
+    pub website: Option<String>,
+}
+
+///Body of PUT /event/{id}/series, null takes the event out of its series
+#[derive(Debug, Deserialize)]
+pub struct EventSeriesLink {
+    pub series_id: Option<i64>,
+}
+
 ///using the address or the long and lat to get an address so we can tell people what events are nearby
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Location {
@@ -90,6 +188,16 @@ pub struct Location {
     pub latitude: f64,
     pub venue_name: Option<String>,
     pub parking_info: Option<String>,
+    ///IANA name, e.g. "America/Denver", times on site are shown in it
+    #[serde(default)]
+    pub time_zone: Option<String>,
+}
+
+impl Location {
+    ///None when unset or not a zone we know, callers fall back to UTC
+    pub fn tz(&self) -> Option<Tz> {
+        self.time_zone.as_deref().and_then(|tz| tz.parse().ok())
+    }
 }
 
 ///Rather comprehensive list of things to consider when camping
@@ -273,6 +381,8 @@ impl CampingProfile {
 }
 
 ///Fixing issues caused by the enrichment of the data by AI
+// Stored JSON is upgraded by models::stored_schema on read, so once the admin
+// upgrade-data jobs have run everywhere this is only needed for request bodies.
 // Then define the deserializer
 fn deserialize_hookups<'de, D>(deserializer: D) -> Result<Option<Hookups>, D::Error>
 where
@@ -0,0 +1,117 @@
+// ============================================================================
+// src/models/geojson.rs - GeoJSON (RFC 7946) output for map clients
+// ============================================================================
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize)]
+pub struct FeatureCollection<P, G = PointGeometry> {
+    #[serde(rename = "type")]
+    pub kind: &'static str, // always "FeatureCollection"
+    pub features: Vec<Feature<P, G>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Feature<P, G = PointGeometry> {
+    #[serde(rename = "type")]
+    pub kind: &'static str, // always "Feature"
+    pub id: i64,
+    pub geometry: G,
+    pub properties: P,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PointGeometry {
+    #[serde(rename = "type")]
+    pub kind: &'static str, // always "Point"
+    pub coordinates: [f64; 2], // [longitude, latitude]
+}
+
+///Points and outlines in the same collection, for venue maps
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub enum Geometry {
+    Point { coordinates: [f64; 2] },
+    Polygon { coordinates: Vec<Vec<[f64; 2]>> },
+}
+
+impl<P, G> FeatureCollection<P, G> {
+    pub fn new(features: Vec<Feature<P, G>>) -> Self {
+        Self {
+            kind: "FeatureCollection",
+            features,
+        }
+    }
+}
+
+impl<P, G> Feature<P, G> {
+    pub fn new(id: i64, geometry: G, properties: P) -> Self {
+        Self {
+            kind: "Feature",
+            id,
+            geometry,
+            properties,
+        }
+    }
+}
+
+impl<P> Feature<P> {
+    pub fn point(id: i64, longitude: f64, latitude: f64, properties: P) -> Self {
+        Self {
+            kind: "Feature",
+            id,
+            geometry: PointGeometry {
+                kind: "Point",
+                coordinates: [longitude, latitude],
+            },
+            properties,
+        }
+    }
+}
+
+///Just enough to draw and label a marker, the full event is one GET away
+#[derive(Debug, Serialize)]
+pub struct EventFeatureProperties {
+    pub name: String,
+    pub event_type_id: Option<i64>,
+    pub event_type: String,
+    pub category: String,
+    pub map_indicator: String,
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub camping_allowed: bool,
+}
+
+///A microevent placed at its stage or area, see VenueLogic::get_microevent_map
+#[derive(Debug, Serialize)]
+pub struct MicroeventFeatureProperties {
+    pub name: String,
+    pub area_id: i64,
+    pub area_name: String,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterQueryParams {
+    pub zoom: u8, // web map zoom, 0 = whole world
+}
+
+///Clusters below the zoom threshold, individual events above it
+#[derive(Debug, Serialize)]
+pub struct MapClusterResponse {
+    pub zoom: u8,
+    pub clustered: bool,
+    pub cell_size_degrees: Option<f64>,
+    pub clusters: Vec<MapCluster>,
+    pub events: Option<FeatureCollection<EventFeatureProperties>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MapCluster {
+    pub latitude: f64, // centroid of the events in the cell
+    pub longitude: f64,
+    pub count: usize,
+    pub categories: BTreeMap<String, usize>, // EventType.category -> count
+}
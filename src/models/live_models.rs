@@ -0,0 +1,118 @@
+use crate::errors::AppError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleChangeKind {
+    Created,
+    Updated,
+    Archived,
+    Deleted,
+}
+
+///A change to an event or to one of its microevents, as pushed to live subscribers
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleChange {
+    ///Position in the change feed, assigned when published
+    pub seq: u64,
+    pub event_id: i64,
+    ///None when the event itself changed
+    pub microevent_id: Option<i64>,
+    pub change: ScheduleChangeKind,
+    ///The event after the change, for event changes that were not deletes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<Value>,
+    ///The microevent after the change, for microevent changes that were not deletes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub microevent: Option<Value>,
+}
+
+impl ScheduleChange {
+    pub fn for_event(event_id: i64, change: ScheduleChangeKind, event: Option<Value>) -> Self {
+        Self {
+            seq: 0,
+            event_id,
+            microevent_id: None,
+            change,
+            event,
+            microevent: None,
+        }
+    }
+
+    pub fn for_microevent(
+        event_id: i64,
+        microevent_id: i64,
+        change: ScheduleChangeKind,
+        microevent: Option<Value>,
+    ) -> Self {
+        Self {
+            seq: 0,
+            event_id,
+            microevent_id: Some(microevent_id),
+            change,
+            event: None,
+            microevent,
+        }
+    }
+}
+
+///What a WebSocket client can send
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum LiveClientMessage {
+    Subscribe { event_id: i64 },
+    Unsubscribe { event_id: i64 },
+}
+
+///What the server sends back
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LiveServerMessage<'a> {
+    Subscribed {
+        event_id: i64,
+    },
+    Unsubscribed {
+        event_id: i64,
+    },
+    ScheduleChange(&'a ScheduleChange),
+    ///The connection fell behind and missed changes, reload the schedules
+    Resync {
+        missed: u64,
+    },
+    Error {
+        message: String,
+    },
+}
+
+///GET /sse?event_ids=1,2,3
+#[derive(Debug, Deserialize)]
+pub struct LiveStreamParams {
+    #[serde(default)]
+    pub event_ids: String,
+    ///For clients that can't set the Last-Event-ID header on reconnect
+    pub last_event_id: Option<String>,
+}
+
+impl LiveStreamParams {
+    pub fn event_ids(&self) -> Result<Vec<i64>, AppError> {
+        let ids = self
+            .event_ids
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| {
+                id.parse::<i64>()
+                    .map_err(|_| AppError::ValidationError(format!("Invalid event id {}", id)))
+            })
+            .collect::<Result<Vec<i64>, AppError>>()?;
+
+        if ids.is_empty() {
+            return Err(AppError::ValidationError(
+                "event_ids is required, e.g. ?event_ids=1,2".to_string(),
+            ));
+        }
+
+        Ok(ids)
+    }
+}
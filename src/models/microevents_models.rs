@@ -1,5 +1,6 @@
+use crate::errors::AppError;
 use crate::models::event_models::*;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
@@ -20,12 +21,68 @@ pub struct Microevent {
     pub start_time: Option<DateTime<Utc>>,
     #[serde(default)]
     pub end_time: Option<DateTime<Utc>>,
+    ///Stage, tent or zone inside the event, see venue_models
+    #[serde(default)]
+    pub area_id: Option<i64>,
+    ///Track or category inside the event, see MicroeventTrack
+    #[serde(default)]
+    pub track_id: Option<i64>,
+    ///RRULE subset, e.g. "FREQ=DAILY", only on the first occurrence of a series
+    #[serde(default)]
+    pub recurrence_rule: Option<String>,
+    ///The first occurrence, which holds the rule, for the other occurrences
+    #[serde(default)]
+    pub recurrence_parent_id: Option<i64>,
+    ///Which date of the series this occurrence stands for
+    #[serde(default)]
+    pub occurrence_date: Option<NaiveDate>,
+    ///Edited on its own, changes to the series no longer touch it
+    #[serde(default)]
+    pub detached: bool,
+    #[serde(default)]
+    pub cancelled: bool,
+    ///Seats for RSVPs, unlimited when None
+    #[serde(default)]
+    pub capacity: Option<i64>,
+    ///RSVPs holding a seat, read only
+    #[serde(default)]
+    pub attendee_count: i64,
+    ///Read only, None when there is no capacity
+    #[serde(default)]
+    pub seats_remaining: Option<i64>,
+    ///Read only, set by the event's owner when the event requires approval
+    #[serde(default)]
+    pub approval_status: ApprovalStatus,
+    ///Read only, why it was rejected
+    #[serde(default)]
+    pub review_note: Option<String>,
     #[serde(default)]
     pub created_at: Option<DateTime<Utc>>,
     #[serde(default)]
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+//-- microevents.approval_status holds:
+//'approved'  -- on the schedule
+//'pending'   -- waiting in the event owner's queue
+//'rejected'  -- only visible to the submitter and the event owner
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    #[default]
+    Approved,
+    Pending,
+    Rejected,
+}
+
+///Body of POST /microevent/{id}/reject
+#[derive(Debug, Default, Deserialize)]
+pub struct MicroeventReview {
+    ///Passed on to the submitter
+    pub note: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EventWithMicroevents {
     #[serde(flatten)]
@@ -46,3 +103,213 @@ pub struct CreateMicroeventRequest {
     #[serde(default)]
     pub end_time: DateTime<Utc>,
 }
+
+///A user's saved and favorited microevents for one event, in start time order
+#[derive(Debug, Serialize)]
+pub struct PersonalSchedule {
+    pub event_id: i64,
+    ///The event's tracks, to label and filter the items by
+    pub tracks: Vec<MicroeventTrack>,
+    pub items: Vec<ScheduleItem>,
+    pub conflicts: Vec<ScheduleConflict>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScheduleItem {
+    #[serde(flatten)]
+    pub microevent: Microevent,
+    pub saved: bool,
+    pub favorite: bool,
+    pub conflicts_with: Vec<i64>,
+}
+
+///Two microevents whose start_time/end_time windows overlap
+#[derive(Debug, Serialize, Clone)]
+pub struct ScheduleConflict {
+    pub first_id: i64,
+    pub second_id: i64,
+    pub overlap_start: DateTime<Utc>,
+    pub overlap_end: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveToggleParams {
+    #[serde(default)]
+    pub include_conflicts: bool,
+    #[serde(default)]
+    pub scope: ToggleScope,
+}
+
+///?scope=series saves or favorites every occurrence of a recurring microevent
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ToggleScope {
+    #[default]
+    Occurrence,
+    Series,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ToggleScopeParams {
+    #[serde(default)]
+    pub scope: ToggleScope,
+}
+
+///A track or category within one event, e.g. workshops, music, kids
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct MicroeventTrack {
+    pub id: i64,
+    pub event_id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub color: Option<String>, // "#rrggbb"
+    pub sort_order: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MicroeventTrackRequest {
+    #[serde(default)]
+    pub name: String,
+    pub description: Option<String>,
+    pub color: Option<String>,
+    #[serde(default)]
+    pub sort_order: i64,
+}
+
+///GET /event/{id}/microevent?track_ids=1,2 and the same on /event/{id}/schedule
+#[derive(Debug, Deserialize, Default)]
+pub struct MicroeventFilterParams {
+    ///Comma separated, "none" matches microevents without a track
+    pub track_ids: Option<String>,
+    ///Archived microevents are left out unless asked for, and then only
+    ///shown to the event's owner, their own organizers and admins
+    #[serde(default)]
+    pub include_archived: bool,
+    ///Cancelled occurrences are left out unless asked for
+    #[serde(default)]
+    pub include_cancelled: bool,
+}
+
+impl MicroeventFilterParams {
+    ///None when there is nothing to filter on
+    pub fn track_filter(&self) -> Result<Option<Vec<Option<i64>>>, AppError> {
+        let Some(raw) = self.track_ids.as_deref() else {
+            return Ok(None);
+        };
+
+        let tracks = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| match id {
+                "none" => Ok(None),
+                _ => id
+                    .parse::<i64>()
+                    .map(Some)
+                    .map_err(|_| AppError::ValidationError(format!("Invalid track id {}", id))),
+            })
+            .collect::<Result<Vec<Option<i64>>, AppError>>()?;
+
+        Ok(if tracks.is_empty() {
+            None
+        } else {
+            Some(tracks)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RsvpStatus {
+    Going,
+    Waitlisted,
+}
+
+impl RsvpStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RsvpStatus::Going => "going",
+            RsvpStatus::Waitlisted => "waitlisted",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "going" => RsvpStatus::Going,
+            _ => RsvpStatus::Waitlisted,
+        }
+    }
+}
+
+///Answer to POST /microevent/{id}/rsvp
+#[derive(Debug, Serialize)]
+pub struct RsvpResult {
+    pub microevent_id: i64,
+    pub status: RsvpStatus,
+    ///1 is next in line, only for the waitlist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waitlist_position: Option<i64>,
+    pub seats_remaining: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Attendee {
+    pub user_id: String,
+    pub user_name: Option<String>,
+    pub rsvp_at: String,
+}
+
+///GET /microevent/{id}/attendees, for the owner
+#[derive(Debug, Serialize)]
+pub struct AttendeeList {
+    pub microevent_id: i64,
+    pub capacity: Option<i64>,
+    pub seats_remaining: Option<i64>,
+    pub going: Vec<Attendee>,
+    ///In promotion order
+    pub waitlist: Vec<Attendee>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NowParams {
+    ///How far ahead "up next" looks, 60 minutes when left out
+    pub window_minutes: Option<i64>,
+}
+
+impl NowParams {
+    pub fn window(&self) -> Result<Duration, AppError> {
+        match self.window_minutes {
+            None => Ok(Duration::minutes(60)),
+            Some(minutes) if (1..=1440).contains(&minutes) => Ok(Duration::minutes(minutes)),
+            Some(_) => Err(AppError::ValidationError(
+                "window_minutes must be between 1 and 1440".to_string(),
+            )),
+        }
+    }
+}
+
+///A microevent with its times in the event's time zone
+#[derive(Debug, Serialize)]
+pub struct NowEntry {
+    #[serde(flatten)]
+    pub microevent: Microevent,
+    pub local_start: Option<DateTime<FixedOffset>>,
+    pub local_end: Option<DateTime<FixedOffset>>,
+}
+
+///GET /event/{id}/now
+#[derive(Debug, Serialize)]
+pub struct HappeningNow {
+    pub event_id: i64,
+    ///The event's time zone, UTC when it has none
+    pub time_zone: String,
+    pub now: DateTime<FixedOffset>,
+    pub window_minutes: i64,
+    ///Started and not yet over, by start time
+    pub happening_now: Vec<NowEntry>,
+    ///Starting within the window, by start time
+    pub up_next: Vec<NowEntry>,
+    ///The next time this answer changes without anyone editing the schedule.
+    ///Poll again then, or sooner on a schedule_change from /sse.
+    pub refresh_at: Option<DateTime<Utc>>,
+}
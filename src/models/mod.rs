@@ -1,6 +1,15 @@
 pub mod analytics;
 pub mod database_models;
 pub mod dto;
+pub mod email_models;
 pub mod event_models;
+pub mod geojson;
+pub mod live_models;
 pub mod microevents_models;
+pub mod notification_models;
+pub mod saved_search_models;
+pub mod stored_schema;
+pub mod trip_models;
 pub mod user;
+pub mod venue_models;
+pub mod webhook_models;
@@ -0,0 +1,104 @@
+use crate::models::database_models::NotificationRow;
+use serde::{Deserialize, Serialize};
+
+///What a notification is about. Stored as the notifications.category column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCategory {
+    SavedSearch,
+    FavoriteEvent,
+    System,
+}
+
+impl NotificationCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationCategory::SavedSearch => "saved_search",
+            NotificationCategory::FavoriteEvent => "favorite_event",
+            NotificationCategory::System => "system",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationChannel {
+    InApp,
+    Email,
+}
+
+///Typed form of UserRow.notification_preferences.
+///A channel switched off at the top level is off for every category.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationPreferences {
+    #[serde(default = "default_true")]
+    pub in_app: bool,
+    #[serde(default)]
+    pub email: bool,
+    #[serde(default)]
+    pub saved_searches: CategoryPreference,
+    #[serde(default)]
+    pub favorite_events: CategoryPreference,
+    #[serde(default)]
+    pub system: CategoryPreference,
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            in_app: true,
+            email: false,
+            saved_searches: CategoryPreference::default(),
+            favorite_events: CategoryPreference::default(),
+            system: CategoryPreference::default(),
+        }
+    }
+}
+
+impl NotificationPreferences {
+    pub fn allows(&self, category: NotificationCategory, channel: NotificationChannel) -> bool {
+        let preference = match category {
+            NotificationCategory::SavedSearch => &self.saved_searches,
+            NotificationCategory::FavoriteEvent => &self.favorite_events,
+            NotificationCategory::System => &self.system,
+        };
+
+        match channel {
+            NotificationChannel::InApp => self.in_app && preference.in_app,
+            NotificationChannel::Email => self.email && preference.email,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryPreference {
+    #[serde(default = "default_true")]
+    pub in_app: bool,
+    #[serde(default = "default_true")]
+    pub email: bool,
+}
+
+impl Default for CategoryPreference {
+    fn default() -> Self {
+        Self {
+            in_app: true,
+            email: true,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationQueryParams {
+    #[serde(default)]
+    pub unread_only: bool,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationListResponse {
+    pub unread_count: i64,
+    pub notifications: Vec<NotificationRow>,
+}
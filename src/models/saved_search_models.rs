@@ -0,0 +1,28 @@
+use crate::models::dto::EventQueryParams;
+use serde::{Deserialize, Serialize};
+
+///A search a user runs again and again, optionally with alerts for new matches
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedSearch {
+    #[serde(default)]
+    pub id: Option<i64>,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub query: EventQueryParams,
+    #[serde(default = "default_alerts_enabled")]
+    pub alerts_enabled: bool,
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+fn default_alerts_enabled() -> bool {
+    true
+}
+
+///Result of one pass of the saved search alert job
+#[derive(Debug, Serialize, Default)]
+pub struct SavedSearchAlertReport {
+    pub searches_checked: usize,
+    pub notifications_created: usize,
+}
@@ -0,0 +1,174 @@
+// ============================================================================
+// src/models/stored_schema.rs - Versioning for the JSON we keep in the database
+// ============================================================================
+//
+// events.event_data and camping_profiles.camping_data are written with a
+// schema_version. Anything older is upgraded one version at a time on read,
+// so the structs only ever see the latest shape.
+//
+// Version history:
+//   0 - no schema_version, hookups_available / generator_options could be bare booleans
+//   1 - hookups_available and generator_options are always objects (or null)
+use serde::Serialize;
+use serde::de::{DeserializeOwned, Error as _};
+use serde_json::{Value, json};
+
+pub const CURRENT_SCHEMA_VERSION: u64 = 1;
+
+#[derive(Debug, Clone, Copy)]
+pub enum StoredKind {
+    Event,          // NomEvent, camping info lives under "camping_info"
+    CampingProfile, // CampingProfile, camping info is the root object
+}
+
+// UPGRADES[n] takes a document from version n to n + 1
+const UPGRADES: &[fn(StoredKind, &mut Value)] = &[upgrade_v0_to_v1];
+
+pub fn stored_version(value: &Value) -> u64 {
+    value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+/// Runs every upgrade between the stored version and the current one.
+/// Returns true if anything was upgraded.
+pub fn upgrade(kind: StoredKind, value: &mut Value) -> Result<bool, serde_json::Error> {
+    let version = stored_version(value);
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(serde_json::Error::custom(format!(
+            "schema_version {} is newer than this build supports ({})",
+            version, CURRENT_SCHEMA_VERSION
+        )));
+    }
+
+    for step in &UPGRADES[version as usize..] {
+        step(kind, value);
+    }
+
+    if let Some(object) = value.as_object_mut() {
+        object.insert("schema_version".to_string(), json!(CURRENT_SCHEMA_VERSION));
+    }
+
+    Ok(version < CURRENT_SCHEMA_VERSION)
+}
+
+/// Parses stored JSON, upgrading it to the current version first
+pub fn from_stored_json<T: DeserializeOwned>(
+    kind: StoredKind,
+    raw: &str,
+) -> Result<T, serde_json::Error> {
+    let mut value: Value = serde_json::from_str(raw)?;
+    upgrade(kind, &mut value)?;
+    serde_json::from_value(value)
+}
+
+/// Serializes for storage, stamped with the current version
+pub fn to_stored_json<T: Serialize>(data: &T) -> Result<String, serde_json::Error> {
+    let mut value = serde_json::to_value(data)?;
+    if let Some(object) = value.as_object_mut() {
+        object.insert("schema_version".to_string(), json!(CURRENT_SCHEMA_VERSION));
+    }
+    serde_json::to_string(&value)
+}
+
+// Same rules as deserialize_hookups / deserialize_generator_options
+fn upgrade_v0_to_v1(kind: StoredKind, value: &mut Value) {
+    let camping = match kind {
+        StoredKind::Event => value.get_mut("camping_info"),
+        StoredKind::CampingProfile => Some(value),
+    };
+    let Some(camping) = camping.and_then(Value::as_object_mut) else {
+        return;
+    };
+
+    if let Some(hookups) = camping
+        .get_mut("rv_camping")
+        .and_then(|rv| rv.get_mut("hookups_available"))
+    {
+        match hookups {
+            Value::Bool(true) => {
+                *hookups =
+                    json!({ "electric": true, "water": true, "sewer": true, "amp_service": null })
+            }
+            Value::Bool(false) => *hookups = Value::Null,
+            _ => {}
+        }
+    }
+
+    if let Some(generators) = camping.get_mut("generator_options")
+        && let Value::Bool(allowed) = *generators
+    {
+        *generators = json!({
+            "generators_allowed": allowed,
+            "quiet_hours": null,
+            "max_decibel_limit": null,
+            "inverter_generators_only": false,
+            "propane_generators_allowed": allowed,
+            "gasoline_generators_allowed": allowed,
+            "diesel_generators_allowed": allowed,
+            "designated_generator_areas": false,
+            "distance_from_neighbors_feet": null,
+            "fuel_storage_restrictions": null
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::event_models::NomEvent;
+
+    fn v0_event() -> Value {
+        json!({
+            "name": "Rally",
+            "camping_info": {
+                "rv_camping": { "hookups_available": true },
+                "generator_options": false
+            }
+        })
+    }
+
+    #[test]
+    fn v0_booleans_upgrade_to_objects() {
+        let mut value = v0_event();
+        assert!(upgrade(StoredKind::Event, &mut value).unwrap());
+
+        assert_eq!(value["schema_version"], json!(1));
+        let camping = &value["camping_info"];
+        assert_eq!(
+            camping["rv_camping"]["hookups_available"],
+            json!({ "electric": true, "water": true, "sewer": true, "amp_service": null })
+        );
+        assert_eq!(
+            camping["generator_options"]["generators_allowed"],
+            json!(false)
+        );
+    }
+
+    #[test]
+    fn v0_event_deserializes_after_upgrade() {
+        let event: NomEvent = from_stored_json(StoredKind::Event, &v0_event().to_string()).unwrap();
+
+        let camping = event.camping_info.unwrap();
+        let hookups = camping.rv_camping.hookups_available.unwrap();
+        assert!(hookups.electric && hookups.water && hookups.sewer);
+        assert!(!camping.generator_options.unwrap().generators_allowed);
+    }
+
+    #[test]
+    fn upgrade_leaves_the_current_version_alone() {
+        let mut value = v0_event();
+        upgrade(StoredKind::Event, &mut value).unwrap();
+        let upgraded = value.clone();
+
+        assert!(!upgrade(StoredKind::Event, &mut value).unwrap());
+        assert_eq!(value, upgraded);
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let mut value = json!({ "schema_version": CURRENT_SCHEMA_VERSION + 1 });
+        assert!(upgrade(StoredKind::Event, &mut value).is_err());
+    }
+}
@@ -0,0 +1,32 @@
+use crate::models::dto::EventResponse;
+use serde::Serialize;
+
+///A user's saved events chained together in date order
+#[derive(Debug, Serialize)]
+pub struct TripItinerary {
+    pub stops: Vec<TripStop>,
+    pub legs: Vec<TripLeg>,
+    // Saved events without a start date can't be placed on the route
+    pub unscheduled: Vec<EventResponse>,
+    pub total_miles: f64,
+    pub total_boondocking_nights: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TripStop {
+    pub sequence: usize,
+    pub event: EventResponse,
+}
+
+///The drive between two consecutive stops
+#[derive(Debug, Serialize)]
+pub struct TripLeg {
+    pub from_event_id: Option<i64>,
+    pub to_event_id: Option<i64>,
+    pub distance_miles: f64,
+    // Whole days between leaving one event and the next one starting (negative when they overlap)
+    pub gap_days: i64,
+    pub overlapping: bool,
+    // Nights not covered by late departure / early arrival that need somewhere to park
+    pub boondocking_nights: i64,
+}
@@ -0,0 +1,90 @@
+use crate::models::microevents_models::Microevent;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AreaKind {
+    Stage,
+    Workshop,
+    Camp,
+    Vendor,
+    Facility,
+    #[default]
+    Other,
+}
+
+impl AreaKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AreaKind::Stage => "stage",
+            AreaKind::Workshop => "workshop",
+            AreaKind::Camp => "camp",
+            AreaKind::Vendor => "vendor",
+            AreaKind::Facility => "facility",
+            AreaKind::Other => "other",
+        }
+    }
+}
+
+///Venue area as sent by the client
+#[derive(Debug, Deserialize)]
+pub struct VenueAreaRequest {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub kind: AreaKind,
+    pub description: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    ///Outline as [longitude, latitude] pairs, closed automatically
+    pub polygon: Option<Vec<[f64; 2]>>,
+    ///Column order in grid views, then name
+    #[serde(default)]
+    pub sort_order: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VenueArea {
+    pub id: i64,
+    pub event_id: i64,
+    pub name: String,
+    pub kind: AreaKind,
+    pub description: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub polygon: Option<Vec<[f64; 2]>>,
+    pub sort_order: i64,
+}
+
+///Just enough to draw and label an area on the venue map
+#[derive(Debug, Serialize)]
+pub struct AreaFeatureProperties {
+    pub name: String,
+    pub kind: AreaKind,
+    pub description: Option<String>,
+}
+
+///GET /event/{id}/schedule/grid - one column per area, one row per day
+#[derive(Debug, Serialize)]
+pub struct ScheduleGrid {
+    pub event_id: i64,
+    ///Every column in display order, whether or not it has anything that day
+    pub areas: Vec<VenueArea>,
+    pub days: Vec<ScheduleGridDay>,
+    ///Microevents without a start_time can't be placed on a day
+    pub unscheduled: Vec<Microevent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScheduleGridDay {
+    pub date: NaiveDate,
+    pub columns: Vec<ScheduleGridColumn>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScheduleGridColumn {
+    ///None for the microevents that aren't assigned to an area
+    pub area_id: Option<i64>,
+    pub microevents: Vec<Microevent>,
+}
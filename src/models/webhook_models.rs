@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+pub const EVENT_CREATED: &str = "event.created";
+pub const EVENT_UPDATED: &str = "event.updated";
+pub const EVENT_DELETED: &str = "event.deleted";
+pub const MICROEVENT_CREATED: &str = "microevent.created";
+pub const MICROEVENT_UPDATED: &str = "microevent.updated";
+pub const MICROEVENT_DELETED: &str = "microevent.deleted";
+pub const PING: &str = "ping";
+
+pub const WEBHOOK_EVENT_TYPES: &[&str] = &[
+    EVENT_CREATED,
+    EVENT_UPDATED,
+    EVENT_DELETED,
+    MICROEVENT_CREATED,
+    MICROEVENT_UPDATED,
+    MICROEVENT_DELETED,
+];
+
+///Webhook subscription as sent by the client
+#[derive(Debug, Deserialize)]
+pub struct WebhookRequest {
+    pub url: String,
+    ///Event types to receive, all of them when empty
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default = "default_active")]
+    pub active: bool,
+    ///"all" (admins only) or "owned", defaults to "all" for admins
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct Webhook {
+    pub id: i64,
+    pub owner_user_id: String,
+    pub scope: String,
+    pub url: String,
+    pub events: Vec<String>,
+    pub active: bool,
+    pub created_at: String,
+    ///Only returned when the webhook is created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+///Result of POST /webhooks/{id}/ping, delivered right away without retries
+#[derive(Debug, Serialize)]
+pub struct WebhookPingResult {
+    pub delivery_id: i64,
+    pub delivered: bool,
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+}
+
+///Result of one pass of the delivery job
+#[derive(Debug, Serialize, Default)]
+pub struct WebhookSendReport {
+    pub delivered: usize,
+    pub retrying: usize,
+    pub failed: usize,
+}
@@ -0,0 +1,62 @@
+use crate::AppState;
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use axum::Extension;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+// POST /user/calendar/token - new secret feed URL, shown once
+pub async fn create_token(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    //get the user id
+    let user_id = &claims.sub;
+
+    let token = service.calendar_logic.create_feed_token(user_id).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "token": token,
+            "path": format!("/calendar/{}", token),
+            "message": "Calendar feed created, keep this URL private"
+        })),
+    ))
+}
+
+// DELETE /user/calendar/token - revoke every feed URL for the user
+pub async fn revoke_tokens(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    //get the user id
+    let user_id = &claims.sub;
+
+    let revoked = service.calendar_logic.revoke_feed_tokens(user_id).await?;
+
+    Ok(Json(json!({
+        "revoked": revoked,
+        "message": "Calendar feeds revoked"
+    })))
+}
+
+// GET /calendar/{token} - subscribable feed, the token is the only credential
+pub async fn feed(
+    Path(token): Path<String>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let token = token.strip_suffix(".ics").unwrap_or(&token);
+    let ics = service.calendar_logic.user_feed(token).await?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        ics,
+    ))
+}
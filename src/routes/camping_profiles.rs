@@ -87,3 +87,14 @@ pub async fn apply_camping_profile(
 
     Ok(Json(camping_info))
 }
+
+// POST /campingprofile/upgrade-data (super admin) - rewrite stored camping_data to the current schema_version
+pub async fn upgrade_stored_data(
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let report = service
+        .camping_profile_logic
+        .upgrade_stored_profiles()
+        .await?;
+    Ok(Json(report))
+}
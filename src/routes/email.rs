@@ -0,0 +1,39 @@
+use crate::AppState;
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use axum::Extension;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+// POST /user/email/verify - emails a confirmation link to the account address
+pub async fn request_verification(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service.mail_logic.request_verification(&claims.sub).await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "message": "Verification email sent"
+        })),
+    ))
+}
+
+// GET /verify-email/{token} - the link from that email, no login needed
+pub async fn verify(
+    Path(token): Path<String>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service.mail_logic.verify_email(&token).await?;
+
+    Ok(Json(json!({
+        "message": "Email address verified"
+    })))
+}
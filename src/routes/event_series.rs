@@ -0,0 +1,134 @@
+use crate::AppState;
+use crate::errors::AppError;
+use crate::models::event_models::{EventSeries, EventSeriesLink};
+use crate::models::user::Claims;
+use axum::Extension;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+pub async fn get_all(State(service): State<Arc<AppState>>) -> Result<impl IntoResponse, AppError> {
+    let series = service.event_series_logic.get_all_series().await?;
+    Ok(Json(series))
+}
+
+pub async fn get(
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let series = service.event_series_logic.get_series(id).await?;
+    Ok(Json(series))
+}
+
+pub async fn create(
+    State(service): State<Arc<AppState>>,
+    Json(series): Json<EventSeries>,
+) -> Result<impl IntoResponse, AppError> {
+    let id = service.event_series_logic.create_series(series).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "id": id,
+            "message": "Event series created successfully"
+        })),
+    ))
+}
+
+pub async fn update(
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+    Json(series): Json<EventSeries>,
+) -> Result<impl IntoResponse, AppError> {
+    service.event_series_logic.update_series(id, series).await?;
+
+    Ok(Json(json!({
+        "message": "Event series updated successfully"
+    })))
+}
+
+pub async fn delete(
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service.event_series_logic.delete_series(id).await?;
+
+    Ok(Json(json!({
+        "message": "Event series deleted successfully"
+    })))
+}
+
+// PUT /event/{id}/series - {"series_id": 3}, or null to leave the series
+pub async fn set_event_series(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+    Json(link): Json<EventSeriesLink>,
+) -> Result<impl IntoResponse, AppError> {
+    service
+        .event_series_logic
+        .set_event_series(id, link.series_id, claims)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Event series updated successfully"
+    })))
+}
+
+// POST /event/{id}/next-edition - copy the event one year forward into its series
+pub async fn clone_to_next_edition(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let new_id = service
+        .event_series_logic
+        .clone_to_next_edition(id, claims)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "id": new_id,
+            "message": "Next edition created successfully"
+        })),
+    ))
+}
+
+pub async fn favorite_toggle(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    //get the user id
+    let user_id = &claims.sub;
+
+    service
+        .user_collection_logic
+        .series_favorite_toggle(id, user_id)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Event series favorite toggled!"
+    })))
+}
+
+pub async fn get_favorite_series(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    //get the user id
+    let user_id = &claims.sub;
+
+    let output = service
+        .event_series_logic
+        .get_favorite_series(user_id)
+        .await?;
+
+    Ok(Json(output))
+}
@@ -1,15 +1,16 @@
 use crate::AppState;
 use crate::errors::AppError;
 use crate::logic::EventLogic;
-use crate::models::dto::EventQueryParams;
+use crate::models::dto::{EventQueryParams, ImportRequest};
 use crate::models::event_models::NomEvent;
+use crate::models::geojson::ClusterQueryParams;
 use crate::models::user::Claims;
 use axum::Extension;
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
 };
 use serde_json::json;
 use std::sync::Arc;
@@ -19,12 +20,39 @@ pub async fn get_all(State(service): State<Arc<AppState>>) -> Result<impl IntoRe
     Ok(Json(events))
 }
 
+// GET /event/{id} - JSON, or iCalendar for /event/{id}.ics
+// (the router can't match a suffix after a parameter, so it is split off here)
 pub async fn get(
-    Path(id): Path<i64>,
+    Path(id): Path<String>,
     State(service): State<Arc<AppState>>,
-) -> Result<impl IntoResponse, AppError> {
-    let event = service.event_logic.get_event_by_id(id).await?;
-    Ok(Json(event))
+) -> Result<Response, AppError> {
+    if let Some(id) = id.strip_suffix(".ics") {
+        let id = parse_event_id(id)?;
+        let ics = service.calendar_logic.event_ics(id).await?;
+
+        return Ok((
+            [
+                (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+                (
+                    header::CONTENT_DISPOSITION,
+                    &format!("attachment; filename=\"event-{}.ics\"", id),
+                ),
+            ],
+            ics,
+        )
+            .into_response());
+    }
+
+    let event = service
+        .event_logic
+        .get_event_by_id(parse_event_id(&id)?)
+        .await?;
+    Ok(Json(event).into_response())
+}
+
+fn parse_event_id(id: &str) -> Result<i64, AppError> {
+    id.parse()
+        .map_err(|_| AppError::BadRequest(format!("Invalid event id: {}", id)))
 }
 
 pub async fn search(
@@ -35,33 +63,75 @@ pub async fn search(
     println!("lon: {}", params.longitude.unwrap_or(0.0).to_string());
     println!("rad: {}", params.radius_miles.unwrap_or(0.0).to_string());
 
-    // If lat/lon/radius provided, search by location
-    if let (Some(lat), Some(lon), Some(radius)) =
-        (params.latitude, params.longitude, params.radius_miles)
-    {
-        let events = service
-            .event_logic
-            .get_nearby_events(lat, lon, radius)
-            .await?;
-        return Ok(Json(events));
-    }
-
-    // If event_type provided, search by type
-    if let Some(event_type) = params.event_type {
-        //let type_id: i64 = event_type.id();
-        //let events = service.event_logic.get_events_by_type(type_id).await?;
-        let events = service.event_logic.get_events_by_type(event_type).await?;
-        return Ok(Json(events));
-    }
-
-    // Otherwise return all
-    let events = service.event_logic.get_all_events().await?;
+    // Location, type, camping and amenity filters are all combined.
+    // With no filters set this is the same as get_all.
+    let events = service.event_logic.search_events(&params).await?;
 
     //println!("number of events found: {}", events.iter().count());
 
     Ok(Json(events))
 }
 
+// POST /admin/event/import - JSON or CSV batch, see ImportRequest
+pub async fn import(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+    Json(request): Json<ImportRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let report = service.event_import_logic.import(request, claims).await?;
+
+    let status = if report.dry_run || !report.errors.is_empty() {
+        StatusCode::OK
+    } else {
+        StatusCode::CREATED
+    };
+
+    Ok((status, Json(report)))
+}
+
+// GET /event/integrity (super admin) - list events with problems and the reason
+pub async fn integrity_scan(
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let report = service.event_logic.scan_integrity().await?;
+    Ok(Json(report))
+}
+
+// POST /event/upgrade-data (super admin) - rewrite stored event_data to the current schema_version
+pub async fn upgrade_stored_data(
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let report = service.event_logic.upgrade_stored_events().await?;
+    Ok(Json(report))
+}
+
+// GET /event/geojson - FeatureCollection for the map, takes the same filters as search plus bbox
+pub async fn geojson(
+    Query(params): Query<EventQueryParams>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let collection = service.event_logic.get_events_geojson(&params).await?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/geo+json")],
+        Json(collection),
+    ))
+}
+
+// GET /event/clusters?zoom=4&bbox=... - map clusters with per-category counts
+pub async fn clusters(
+    Query(params): Query<EventQueryParams>,
+    Query(cluster): Query<ClusterQueryParams>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let output = service
+        .event_logic
+        .get_event_clusters(&params, cluster.zoom)
+        .await?;
+
+    Ok(Json(output))
+}
+
 pub async fn create(
     Extension(claims): Extension<Claims>,
     State(service): State<Arc<AppState>>,
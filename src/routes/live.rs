@@ -0,0 +1,226 @@
+use crate::AppState;
+use crate::errors::AppError;
+use crate::logic::Replay;
+use crate::models::live_models::{
+    LiveClientMessage, LiveServerMessage, LiveStreamParams, ScheduleChange,
+};
+use crate::models::user::Claims;
+use axum::Extension;
+use axum::{
+    extract::{
+        Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::HeaderMap,
+    response::{
+        IntoResponse,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use std::collections::{HashSet, VecDeque};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::sync::broadcast::{Receiver, error::RecvError};
+
+// One connection can follow a whole festival weekend, not the whole catalogue
+const MAX_SUBSCRIPTIONS: usize = 50;
+
+// GET /ws - then send {"action": "subscribe", "event_id": 1}
+pub async fn ws(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+    upgrade: WebSocketUpgrade,
+) -> impl IntoResponse {
+    let changes = service.change_feed.subscribe();
+    upgrade.on_upgrade(move |socket| live_updates(socket, service, changes, claims))
+}
+
+async fn live_updates(
+    mut socket: WebSocket,
+    service: Arc<AppState>,
+    mut changes: Receiver<ScheduleChange>,
+    claims: Claims,
+) {
+    let mut subscriptions: HashSet<i64> = HashSet::new();
+
+    loop {
+        let reply = tokio::select! {
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    handle_client_message(&text, &mut subscriptions, &service).await
+                }
+                // axum answers pings itself
+                Some(Ok(Message::Ping(_) | Message::Pong(_))) => continue,
+                Some(Ok(Message::Binary(_))) => Some(error("Send JSON text messages")),
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+            },
+            change = changes.recv() => match change {
+                Ok(change) if subscriptions.contains(&change.event_id) => {
+                    serde_json::to_string(&LiveServerMessage::ScheduleChange(&change)).ok()
+                }
+                Ok(_) => None,
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!("live updates for user {} lagged by {}", claims.sub, missed);
+                    serde_json::to_string(&LiveServerMessage::Resync { missed }).ok()
+                }
+                Err(RecvError::Closed) => break,
+            },
+        };
+
+        if let Some(reply) = reply
+            && socket.send(Message::Text(reply.into())).await.is_err()
+        {
+            break;
+        }
+    }
+}
+
+async fn handle_client_message(
+    text: &str,
+    subscriptions: &mut HashSet<i64>,
+    service: &AppState,
+) -> Option<String> {
+    let message = match serde_json::from_str::<LiveClientMessage>(text) {
+        Ok(message) => message,
+        Err(e) => return Some(error(&format!("Unknown message: {}", e))),
+    };
+
+    let reply = match message {
+        LiveClientMessage::Subscribe { event_id } => {
+            if subscriptions.len() >= MAX_SUBSCRIPTIONS && !subscriptions.contains(&event_id) {
+                return Some(error(&format!(
+                    "At most {} events per connection",
+                    MAX_SUBSCRIPTIONS
+                )));
+            }
+            if service.event_logic.get_event_by_id(event_id).await.is_err() {
+                return Some(error(&format!("Event {} not found", event_id)));
+            }
+            subscriptions.insert(event_id);
+            LiveServerMessage::Subscribed { event_id }
+        }
+        LiveClientMessage::Unsubscribe { event_id } => {
+            subscriptions.remove(&event_id);
+            LiveServerMessage::Unsubscribed { event_id }
+        }
+    };
+
+    serde_json::to_string(&reply).ok()
+}
+
+fn error(message: &str) -> String {
+    serde_json::to_string(&LiveServerMessage::Error {
+        message: message.to_string(),
+    })
+    .unwrap_or_default()
+}
+
+// GET /sse?event_ids=1,2 - the same changes as /ws, for clients that only
+// speak EventSource. Reconnects send Last-Event-ID and get what they missed.
+pub async fn sse(
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<LiveStreamParams>,
+    headers: HeaderMap,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let event_ids: HashSet<i64> = params.event_ids()?.into_iter().collect();
+    if event_ids.len() > MAX_SUBSCRIPTIONS {
+        return Err(AppError::ValidationError(format!(
+            "At most {} events per connection",
+            MAX_SUBSCRIPTIONS
+        )));
+    }
+    for event_id in &event_ids {
+        service.event_logic.get_event_by_id(*event_id).await?;
+    }
+
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .or(params.last_event_id.as_deref());
+    let (changes, replay) = service.change_feed.resume(last_event_id);
+
+    let mut stream = SseStream {
+        service,
+        changes,
+        event_ids,
+        pending: VecDeque::new(),
+        last_seq: 0,
+        user_id: claims.sub,
+    };
+    match replay {
+        Replay::Fresh => {}
+        Replay::Changes(missed) => stream.queue(missed),
+        Replay::Gap => stream.pending.push_back(resync_event(0)),
+    }
+
+    let events = futures_util::stream::unfold(stream, |mut stream| async move {
+        let event = stream.next().await?;
+        Some((Ok::<_, Infallible>(event), stream))
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+struct SseStream {
+    service: Arc<AppState>,
+    changes: Receiver<ScheduleChange>,
+    event_ids: HashSet<i64>,
+    pending: VecDeque<Event>,
+    // anything at or below this was already sent, or replayed
+    last_seq: u64,
+    user_id: String,
+}
+
+impl SseStream {
+    async fn next(&mut self) -> Option<Event> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+
+            match self.changes.recv().await {
+                Ok(change) => self.queue(vec![change]),
+                // the log usually still has what the channel dropped
+                Err(RecvError::Lagged(missed)) => {
+                    match self.service.change_feed.since(self.last_seq) {
+                        Some(changes) => self.queue(changes),
+                        None => {
+                            tracing::warn!("sse for user {} lagged by {}", self.user_id, missed);
+                            self.pending.push_back(resync_event(missed));
+                        }
+                    }
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    fn queue(&mut self, changes: Vec<ScheduleChange>) {
+        for change in changes {
+            if change.seq <= self.last_seq {
+                continue;
+            }
+            self.last_seq = change.seq;
+            if !self.event_ids.contains(&change.event_id) {
+                continue;
+            }
+
+            let id = self.service.change_feed.event_id(change.seq);
+            if let Ok(event) = Event::default()
+                .event("schedule_change")
+                .id(id)
+                .json_data(&change)
+            {
+                self.pending.push_back(event);
+            }
+        }
+    }
+}
+
+// The client should reload the schedules it shows, then carry on from here
+fn resync_event(missed: u64) -> Event {
+    Event::default()
+        .event("resync")
+        .data(serde_json::json!({ "missed": missed }).to_string())
+}
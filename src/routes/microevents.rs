@@ -4,36 +4,52 @@ use crate::AppState;
 use crate::errors::AppError;
 use crate::logic::MicroeventLogic;
 use crate::models::dto::EventQueryParams;
-use crate::models::microevents_models::Microevent;
+use crate::models::microevents_models::{
+    Microevent, MicroeventFilterParams, MicroeventReview, NowParams,
+};
 use crate::models::user::Claims;
 use axum::Extension;
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
     response::IntoResponse,
 };
+use chrono::Utc;
 use serde_json::json;
 use uuid::Uuid;
 
-pub async fn get_all(State(service): State<Arc<AppState>>) -> Result<impl IntoResponse, AppError> {
-    let events = service.microevent_logic.get_all().await?;
+pub async fn get_all(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let events = service.microevent_logic.get_all(&claims).await?;
     Ok(Json(events))
 }
 
+// GET /event/{id}/microevent?track_ids=1,2 ("none" for the ones without a track)
+// &include_archived=true adds archived ones, for their owners
+// &include_cancelled=true adds cancelled occurrences
 pub async fn get_by_event(
+    Extension(claims): Extension<Claims>,
     Path(id): Path<i64>,
+    Query(filter): Query<MicroeventFilterParams>,
     State(service): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let events = service.microevent_logic.get_by_event(id).await?;
+    let events = service
+        .microevent_logic
+        .get_by_event(id, &filter, &claims)
+        .await?;
     Ok(Json(events))
 }
 
+// GET /microevent/{id} - pending and rejected ones only for the people behind them
 pub async fn get(
+    Extension(claims): Extension<Claims>,
     Path(id): Path<i64>,
     State(service): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let event = service.microevent_logic.get(id).await?;
+    let event = service.microevent_logic.get(id, &claims).await?;
     Ok(Json(event))
 }
 
@@ -47,7 +63,7 @@ pub async fn create(
     // Set user_id on the microevent (remove '&' and add 'mut' above)
     event.user_id = user_id.clone(); // or user_id.to_string() if sub is not String
 
-    let id = service.microevent_logic.create(event).await?;
+    let id = service.microevent_logic.create(event, &claims).await?;
 
     Ok((
         StatusCode::CREATED,
@@ -69,7 +85,7 @@ pub async fn create_by_event(
     // Set user_id on the microevent (remove '&' and add 'mut' above)
     event.user_id = user_id.clone(); // or user_id.to_string() if sub is not String
 
-    let id = service.microevent_logic.create(event).await?;
+    let id = service.microevent_logic.create(event, &claims).await?;
 
     Ok((
         StatusCode::CREATED,
@@ -106,6 +122,134 @@ pub async fn delete(
     })))
 }
 
+// POST /microevent/{id}/cancel - one occurrence is off, the rest of the series stays
+pub async fn cancel(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service
+        .microevent_logic
+        .set_cancelled(id, true, claims)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Event cancelled successfully"
+    })))
+}
+
+// POST /microevent/{id}/restore
+pub async fn restore(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service
+        .microevent_logic
+        .set_cancelled(id, false, claims)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Event restored successfully"
+    })))
+}
+
+// POST /microevent/{id}/archive - hidden from schedules, kept for the record
+pub async fn archive(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service
+        .microevent_logic
+        .set_archived(id, true, claims)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Event archived successfully"
+    })))
+}
+
+// POST /microevent/{id}/unarchive
+pub async fn unarchive(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service
+        .microevent_logic
+        .set_archived(id, false, claims)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Event unarchived successfully"
+    })))
+}
+
+// GET /event/{id}/now?window_minutes=60 - on right now and up next, in the
+// event's time zone. Cached until the answer can next change, at most a minute.
+pub async fn get_now(
+    Path(id): Path<i64>,
+    Query(params): Query<NowParams>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let now = Utc::now();
+    let happening = service.microevent_logic.get_now(id, &params, now).await?;
+
+    let max_age = happening
+        .refresh_at
+        .map_or(60, |at| (at - now).num_seconds().clamp(0, 60));
+
+    Ok((
+        [(header::CACHE_CONTROL, format!("max-age={}", max_age))],
+        Json(happening),
+    ))
+}
+
+// GET /event/{id}/microevent/pending - the event owner's review queue
+pub async fn get_pending(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let pending = service.microevent_logic.get_pending(id, &claims).await?;
+    Ok(Json(pending))
+}
+
+// POST /microevent/{id}/approve - event owner or admin
+pub async fn approve(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service
+        .microevent_logic
+        .review(id, true, None, claims)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Event approved successfully"
+    })))
+}
+
+// POST /microevent/{id}/reject - optional {"note": "..."} is passed on to the submitter
+pub async fn reject(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+    review: Option<Json<MicroeventReview>>,
+) -> Result<impl IntoResponse, AppError> {
+    let note = review.and_then(|Json(review)| review.note);
+    service
+        .microevent_logic
+        .review(id, false, note, claims)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Event rejected successfully"
+    })))
+}
+
 //adding the favorite and saved sections
 //pub async fn save_toggle(
 //Extension(claims): Extension<Claims>,
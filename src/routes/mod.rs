@@ -1,9 +1,20 @@
 // src/routes/mod.rs
 pub mod auth;
+pub mod calendar;
 pub mod camping_profiles;
+pub mod email;
+pub mod event_series;
 pub mod event_type;
 pub mod events;
+pub mod live;
 pub mod microevents;
+pub mod notifications;
 pub mod profile;
+pub mod rsvps;
+pub mod saved_searches;
+pub mod tracks;
+pub mod trip;
 pub mod user;
 pub mod usercollection;
+pub mod venues;
+pub mod webhooks;
@@ -0,0 +1,85 @@
+use crate::AppState;
+use crate::errors::AppError;
+use crate::models::notification_models::{NotificationPreferences, NotificationQueryParams};
+use crate::models::user::Claims;
+use axum::Extension;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::IntoResponse,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+// GET /user/notifications?unread_only=true&limit=50
+pub async fn get_all(
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<NotificationQueryParams>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let notifications = service
+        .notification_service
+        .get_notifications(&claims.sub, &params)
+        .await?;
+    Ok(Json(notifications))
+}
+
+// POST /user/notifications/{id}/read
+pub async fn mark_read(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service
+        .notification_service
+        .mark_read(id, &claims.sub)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Notification marked as read"
+    })))
+}
+
+// POST /user/notifications/read-all
+pub async fn mark_all_read(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let updated = service
+        .notification_service
+        .mark_all_read(&claims.sub)
+        .await?;
+
+    Ok(Json(json!({
+        "updated": updated,
+        "message": "All notifications marked as read"
+    })))
+}
+
+// GET /user/notifications/preferences
+pub async fn get_preferences(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let preferences = service
+        .notification_service
+        .get_preferences(&claims.sub)
+        .await?;
+    Ok(Json(preferences))
+}
+
+// PUT /user/notifications/preferences
+pub async fn update_preferences(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+    Json(preferences): Json<NotificationPreferences>,
+) -> Result<impl IntoResponse, AppError> {
+    service
+        .notification_service
+        .update_preferences(&claims.sub, &preferences)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Notification preferences updated successfully"
+    })))
+}
@@ -0,0 +1,44 @@
+use crate::AppState;
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use axum::Extension;
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+// POST /microevent/{id}/rsvp - a seat, or a place on the waitlist when it is full
+pub async fn rsvp(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let result = service.rsvp_logic.rsvp(id, &claims.sub).await?;
+    Ok(Json(result))
+}
+
+// DELETE /microevent/{id}/rsvp - the freed seat goes to the first on the waitlist
+pub async fn cancel(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service.rsvp_logic.cancel_rsvp(id, &claims.sub).await?;
+
+    Ok(Json(json!({
+        "message": "RSVP cancelled successfully"
+    })))
+}
+
+// GET /microevent/{id}/attendees - owner or admin
+pub async fn attendees(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let attendees = service.rsvp_logic.get_attendees(id, &claims).await?;
+    Ok(Json(attendees))
+}
@@ -0,0 +1,88 @@
+use crate::AppState;
+use crate::errors::AppError;
+use crate::models::saved_search_models::SavedSearch;
+use crate::models::user::Claims;
+use axum::Extension;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+// GET /user/searches
+pub async fn get_all(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let searches = service.saved_search_logic.get_searches(&claims.sub).await?;
+    Ok(Json(searches))
+}
+
+// POST /user/searches
+pub async fn create(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+    Json(search): Json<SavedSearch>,
+) -> Result<impl IntoResponse, AppError> {
+    let id = service
+        .saved_search_logic
+        .create_search(&claims.sub, search)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "id": id,
+            "message": "Search saved successfully"
+        })),
+    ))
+}
+
+// PUT /user/searches/{id}
+pub async fn update(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+    Json(search): Json<SavedSearch>,
+) -> Result<impl IntoResponse, AppError> {
+    service
+        .saved_search_logic
+        .update_search(id, &claims.sub, search)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Saved search updated successfully"
+    })))
+}
+
+// DELETE /user/searches/{id}
+pub async fn delete(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service
+        .saved_search_logic
+        .delete_search(id, &claims.sub)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Saved search deleted successfully"
+    })))
+}
+
+// GET /user/searches/{id}/run
+pub async fn run(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let events = service
+        .saved_search_logic
+        .run_search(id, &claims.sub)
+        .await?;
+    Ok(Json(events))
+}
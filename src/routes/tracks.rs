@@ -0,0 +1,67 @@
+use crate::AppState;
+use crate::errors::AppError;
+use crate::models::microevents_models::MicroeventTrackRequest;
+use crate::models::user::Claims;
+use axum::Extension;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+// GET /event/{id}/tracks - workshops, music, kids ... in display order
+pub async fn get_all(
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let tracks = service.track_logic.get_tracks(id).await?;
+    Ok(Json(tracks))
+}
+
+// POST /event/{id}/tracks - event owner or admin
+pub async fn create(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+    Json(request): Json<MicroeventTrackRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let track = service
+        .track_logic
+        .create_track(id, request, &claims)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(track)))
+}
+
+pub async fn update(
+    Extension(claims): Extension<Claims>,
+    Path((id, track_id)): Path<(i64, i64)>,
+    State(service): State<Arc<AppState>>,
+    Json(request): Json<MicroeventTrackRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let track = service
+        .track_logic
+        .update_track(id, track_id, request, &claims)
+        .await?;
+
+    Ok(Json(track))
+}
+
+// DELETE /event/{id}/tracks/{track_id} - its microevents are kept, without a track
+pub async fn delete(
+    Extension(claims): Extension<Claims>,
+    Path((id, track_id)): Path<(i64, i64)>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service
+        .track_logic
+        .delete_track(id, track_id, &claims)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Track deleted successfully"
+    })))
+}
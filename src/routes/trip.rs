@@ -0,0 +1,40 @@
+use crate::AppState;
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use axum::Extension;
+use axum::{Json, extract::State, http::header, response::IntoResponse};
+use std::sync::Arc;
+
+pub async fn get_trip(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    //get the user id
+    let user_id = &claims.sub;
+
+    let output = service.trip_logic.plan(user_id).await?;
+
+    Ok(Json(output))
+}
+
+pub async fn get_trip_gpx(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    //get the user id
+    let user_id = &claims.sub;
+
+    let itinerary = service.trip_logic.plan(user_id).await?;
+    let gpx = service.trip_logic.to_gpx(&itinerary);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/gpx+xml"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"trip.gpx\"",
+            ),
+        ],
+        gpx,
+    ))
+}
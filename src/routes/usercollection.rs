@@ -3,6 +3,9 @@ use crate::errors::AppError;
 use crate::logic::EventLogic;
 use crate::models::database_models::UserEventDataRow;
 use crate::models::dto::UserCollection;
+use crate::models::microevents_models::{
+    MicroeventFilterParams, SaveToggleParams, ToggleScopeParams,
+};
 use crate::models::user::Claims;
 use axum::Extension;
 use axum::{
@@ -45,6 +48,7 @@ pub async fn sync(
 pub async fn microevent_save_toggle(
     Extension(claims): Extension<Claims>,
     Path(id): Path<i64>,
+    Query(params): Query<SaveToggleParams>,
     State(service): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, AppError> {
     //get the user id
@@ -52,17 +56,32 @@ pub async fn microevent_save_toggle(
 
     service
         .user_collection_logic
-        .microevent_save_toggle(id, user_id)
+        .microevent_save_toggle(id, user_id, params.scope)
         .await?;
 
+    //?include_conflicts=true - warn about overlaps with the rest of the saved schedule
+    if params.include_conflicts {
+        let conflicts = service
+            .user_collection_logic
+            .microevent_save_conflicts(id, user_id)
+            .await?;
+
+        return Ok(Json(json!({
+            "message": "Event save toggled!",
+            "conflicts": conflicts
+        })));
+    }
+
     Ok(Json(json!({
         "message": "Event save toggled!"
     })))
 }
 
+// ?scope=series favorites every occurrence of a recurring microevent
 pub async fn microevent_favorite_toggle(
     Extension(claims): Extension<Claims>,
     Path(id): Path<i64>,
+    Query(params): Query<ToggleScopeParams>,
     State(service): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, AppError> {
     //get the user id
@@ -70,7 +89,7 @@ pub async fn microevent_favorite_toggle(
 
     service
         .user_collection_logic
-        .microevent_favorite_toggle(id, user_id)
+        .microevent_favorite_toggle(id, user_id, params.scope)
         .await?;
 
     Ok(Json(json!({
@@ -203,3 +222,19 @@ pub async fn get_saved_microevents(
 
     Ok(Json(output))
 }
+
+// GET /event/{id}/schedule?track_ids=1,2, include_archived and include_cancelled as on
+// GET /event/{id}/microevent
+pub async fn get_personal_schedule(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    Query(filter): Query<MicroeventFilterParams>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let output = service
+        .user_collection_logic
+        .get_personal_schedule(id, &claims, &filter)
+        .await?;
+
+    Ok(Json(output))
+}
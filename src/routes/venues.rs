@@ -0,0 +1,94 @@
+use crate::AppState;
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::venue_models::VenueAreaRequest;
+use axum::Extension;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+// GET /event/{id}/areas - stages, tents and zones in grid column order
+pub async fn get_all(
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let areas = service.venue_logic.get_areas(id).await?;
+    Ok(Json(areas))
+}
+
+// POST /event/{id}/areas - event owner or admin
+pub async fn create(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+    Json(request): Json<VenueAreaRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let area = service
+        .venue_logic
+        .create_area(id, request, &claims)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(area)))
+}
+
+pub async fn update(
+    Extension(claims): Extension<Claims>,
+    Path((id, area_id)): Path<(i64, i64)>,
+    State(service): State<Arc<AppState>>,
+    Json(request): Json<VenueAreaRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let area = service
+        .venue_logic
+        .update_area(id, area_id, request, &claims)
+        .await?;
+
+    Ok(Json(area))
+}
+
+// DELETE /event/{id}/areas/{area_id} - its microevents become unassigned
+pub async fn delete(
+    Extension(claims): Extension<Claims>,
+    Path((id, area_id)): Path<(i64, i64)>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service
+        .venue_logic
+        .delete_area(id, area_id, &claims)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Area deleted successfully"
+    })))
+}
+
+// GET /event/{id}/areas/map - GeoJSON points and outlines for the venue map
+pub async fn get_map(
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let map = service.venue_logic.get_map(id).await?;
+    Ok(Json(map))
+}
+
+// GET /event/{id}/microevent/map - GeoJSON microevents placed at their areas
+pub async fn get_microevent_map(
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let map = service.venue_logic.get_microevent_map(id).await?;
+    Ok(Json(map))
+}
+
+// GET /event/{id}/schedule/grid - microevents by day and area
+pub async fn get_grid(
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let grid = service.venue_logic.get_grid(id).await?;
+    Ok(Json(grid))
+}
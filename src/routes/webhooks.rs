@@ -0,0 +1,93 @@
+use crate::AppState;
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::webhook_models::WebhookRequest;
+use axum::Extension;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+// GET /webhooks - admins see all, organizers their own
+pub async fn get_all(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let webhooks = service.webhook_logic.get_webhooks(&claims).await?;
+    Ok(Json(webhooks))
+}
+
+pub async fn get(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let webhook = service.webhook_logic.get_webhook(id, &claims).await?;
+    Ok(Json(webhook))
+}
+
+// POST /webhooks - the response holds the signing secret, it is not shown again
+pub async fn create(
+    Extension(claims): Extension<Claims>,
+    State(service): State<Arc<AppState>>,
+    Json(request): Json<WebhookRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let webhook = service
+        .webhook_logic
+        .create_webhook(request, &claims)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(webhook)))
+}
+
+pub async fn update(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+    Json(request): Json<WebhookRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    service
+        .webhook_logic
+        .update_webhook(id, request, &claims)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Webhook updated successfully"
+    })))
+}
+
+pub async fn delete(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    service.webhook_logic.delete_webhook(id, &claims).await?;
+
+    Ok(Json(json!({
+        "message": "Webhook deleted successfully"
+    })))
+}
+
+// GET /webhooks/{id}/deliveries - latest 100
+pub async fn get_deliveries(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let deliveries = service.webhook_logic.get_deliveries(id, &claims).await?;
+    Ok(Json(deliveries))
+}
+
+// POST /webhooks/{id}/ping
+pub async fn ping(
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+    State(service): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let result = service.webhook_logic.ping(id, &claims).await?;
+    Ok(Json(result))
+}